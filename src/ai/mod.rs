@@ -1,77 +1,127 @@
-use crate::components::{
-    requests::MeeleeAttackRequest, BlocksTile, Monster, Name, Player, Position, Viewshed,
+use crate::{
+    algorithms::{dijkstra::downhill, line::has_line_of_fire},
+    components::{
+        combat::{Health, RangedAttack},
+        faction::{Faction, Reaction},
+        monster::{
+            BarkTrigger, HeardSound, Pack, PlannedPath, PlayerMemory, Sleeping, Target, WanderGoal,
+        },
+        requests::{
+            CastSpellRequest, MeeleeAttackRequest, MovementRequest, RangedAttackRequest,
+            SpellTarget,
+        },
+        spell::{Spell, SpellKind, Spellbook},
+        status::StatusEffects,
+        BlocksSight, BlocksTile, Floor, Monster, Player, Position, Stealth, Viewshed,
+    },
+    resources::DijkstraMaps,
 };
 use bevy::{
-    log::{debug, error, warn},
-    prelude::{Commands, Component, Entity, Mut, Query, With, Without},
-    utils::hashbrown::HashSet,
+    log::{debug, trace, warn},
+    prelude::{
+        Commands, Component, Entity, Event, EventWriter, Has, Query, Res, ResMut, With, Without,
+    },
+    utils::hashbrown::{HashMap, HashSet},
 };
 use big_brain::prelude::*;
+use rand::Rng;
+
+/// Picks the nearest visible entity the monster's [Faction] is hostile to as its [Target]. Monster with no such
+/// entity in sight loses its target.
+pub fn select_targets(
+    mut cmd: Commands,
+    monsters: Query<(Entity, &Faction, &Viewshed, &Position), With<Monster>>,
+    candidates: Query<(Entity, &Faction, &Position), With<Health>>,
+    targets: Query<&Target>,
+) {
+    for (monster, faction, viewshed, pos) in monsters.iter() {
+        let nearest = candidates
+            .iter()
+            .filter(|(entity, other, other_pos)| {
+                *entity != monster && faction.is_hostile_to(**other) && viewshed.contains(other_pos)
+            })
+            .min_by_key(|(_, _, other_pos)| pos.distance(**other_pos))
+            .map(|(entity, ..)| entity);
+
+        match (nearest, targets.get(monster).ok()) {
+            (Some(nearest), Some(Target(target))) if nearest == *target => (),
+            (Some(nearest), _) => {
+                trace!(?monster, ?nearest, "picked a target");
+                cmd.entity(monster).insert(Target(nearest));
+            }
+            (None, Some(_)) => {
+                cmd.entity(monster).remove::<Target>();
+            }
+            (None, None) => (),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
-pub struct PlayerVisible;
+pub struct TargetVisible;
 
-pub fn player_visible_scorer_system(
-    viewshed: Query<(&Viewshed, &Position), (With<Monster>, Without<Player>)>,
-    mut score_query: Query<(&Actor, &mut Score), With<PlayerVisible>>,
-    ppos: Query<&Position, With<Player>>,
+/// Monster has a [Target], which is always one it can see.
+/// Sets score of `0.6`
+pub fn target_visible_scorer_system(
+    targets: Query<Has<Target>, With<Monster>>,
+    mut score_query: Query<(&Actor, &mut Score), With<TargetVisible>>,
 ) {
-    let ppos = ppos.single();
-    score_query
-        .iter_mut()
-        .for_each(|(Actor(actor), mut score)| {
-            score.set(
-                viewshed
-                    .get(*actor)
-                    .ok()
-                    .map(|(viewshed, _)| viewshed.contains(ppos).then_some(0.6f32))
-                    .flatten()
-                    .unwrap_or(0f32),
-            );
+    for (Actor(actor), mut score) in score_query.iter_mut() {
+        score.set(match targets.get(*actor) {
+            Ok(true) => 0.6,
+            _ => 0.0,
         });
+    }
 }
 
-#[derive(Debug, Clone, Component, ActionBuilder)]
-pub struct CurseAtPlayer;
-
-pub fn curse_at_player_action_system(
-    mut actors: Query<(&Actor, &mut ActionState), With<CurseAtPlayer>>,
-    names: Query<&Name>,
-) {
-    actors
-        .iter_mut()
-        .for_each(|(Actor(actor), mut state): (&Actor, Mut<ActionState>)| {
-            match *state {
-                ActionState::Requested => {
-                    let name = names
-                        .get(*actor)
-                        .map(ToString::to_string)
-                        .unwrap_or("Unnamed".into());
-                    warn!(%name, "cursing at player");
-                    *state = ActionState::Success;
-                }
-                ActionState::Init => (),
-                _ => error!(?state, "unexpected action state"),
-            };
-        });
+/// Player entity and its position, queried by actions which also move monsters around
+type PlayerPosition<'w, 's> =
+    Query<'w, 's, (Entity, &'static Position), (With<Player>, Without<Monster>)>;
+
+/// Monster wants to shout something at the player. Whether it does and what it says is up to the bark system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CurseAtPlayer {
+    pub monster: Entity,
+    pub trigger: BarkTrigger,
+}
+
+/// Steps down the `map` starting at `from`, the first step only onto a tile accepted by `free`. Further steps are
+/// only planned, so they may lead through others that are going to move anyway. `None` when there is no step
+/// closer to the bottom of the map.
+fn plan_downhill(
+    map: &HashMap<Position, i32>,
+    from: Position,
+    free: impl Fn(&Position) -> bool,
+) -> Option<Vec<Position>> {
+    let mut plan = vec![downhill(map, from, free)?];
+    while let Some(next) = plan.last().and_then(|last| downhill(map, *last, |_| true)) {
+        plan.push(next);
+    }
+    Some(plan)
 }
 
 #[derive(Debug, Clone, Component, ActionBuilder)]
-pub struct ChasePlayer;
+pub struct ChaseTarget;
 
-pub fn chase_player(
-    mut actors: Query<(&Actor, &mut ActionState), With<ChasePlayer>>,
+/// Moves the monster one step closer to its [Target]. The player is chased along the shared approach map, other
+/// targets along the goal map of the tile they stand on. [Pack] members surround their target instead, see
+/// [pack_step].
+pub fn chase_target(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<ChaseTarget>>,
+    mut maps: ResMut<DijkstraMaps>,
     blockers: Query<&Position, (With<BlocksTile>, Without<Monster>)>,
     mut mpos: Query<&mut Position, (With<Monster>, Without<BlocksTile>)>,
-    ppos: Query<&Position, (With<Player>, Without<Monster>)>,
+    player: PlayerPosition,
+    chasers: Query<(&Target, Option<&StatusEffects>, Has<Pack>)>,
 ) {
-    let mut impassable = HashSet::new();
-    impassable.extend(blockers.into_iter().map(|pos| *pos));
-
-    let finish = *ppos.single();
-
-    let mut finish_positions = HashSet::new();
-    finish_positions.extend(finish.possible_successors().into_iter());
+    let impassable = blockers.iter().copied().collect::<HashSet<Position>>();
+    let (player, ppos) = player.single();
+    let ppos = *ppos;
+    // kept up to date as monsters move, so they do not step onto each other
+    let mut occupied = mpos.iter().copied().collect::<HashSet<Position>>();
+    // tiles around the targets already claimed by pack members this turn
+    let mut reserved = HashSet::new();
 
     for (Actor(actor), mut action_state) in actors.iter_mut() {
         if !matches!(*action_state, ActionState::Requested) {
@@ -79,97 +129,826 @@ pub fn chase_player(
             *action_state = ActionState::Success;
             continue;
         };
+        *action_state = ActionState::Success;
 
-        let Ok(monster_pos) = mpos.get(*actor) else {
+        let (Ok(monster_pos), Ok((Target(target), effects, in_pack))) =
+            (mpos.get(*actor).copied(), chasers.get(*actor))
+        else {
             continue;
         };
 
-        let mut monster_pos_set = HashSet::new();
-        monster_pos_set.extend(mpos.iter().map(|pos| *pos));
+        if effects.is_some_and(StatusEffects::is_stunned) {
+            debug!(?actor, "monster is stunned, skipping turn");
+            continue;
+        }
 
-        let Some((path, _cost)) = pathfinding::directed::astar::astar(
-            monster_pos,
-            |p| {
-                p.possible_successors()
-                    .into_iter()
-                    .filter_map(|p| {
-                        (p == finish)
-                            .then_some((p, 1))
-                            .or((!impassable.contains(&p) && !monster_pos_set.contains(&p))
-                                .then_some((p, 1)))
-                    })
-                    .collect::<Vec<(Position, i32)>>()
-            },
-            |p| p.distance(*monster_pos) / 3,
-            |p| finish_positions.contains(p),
-        ) else {
+        let passable =
+            |p: &Position| *p != ppos && !impassable.contains(p) && !occupied.contains(p);
+        // steps the monster is going to take, starting with the next one
+        let plan = match effects.is_some_and(StatusEffects::is_confused) {
+            // confused monster stumbles around instead of following its target
+            true => Some(vec![monster_pos + MovementRequest::random()])
+                .filter(|plan| plan.iter().all(passable)),
+            false if in_pack => {
+                let target_pos = match *target == player {
+                    true => Some(ppos),
+                    false => mpos.get(*target).ok().copied(),
+                };
+                let Some(target_pos) = target_pos else {
+                    continue;
+                };
+                pack_step(
+                    monster_pos,
+                    target_pos,
+                    &impassable,
+                    &occupied,
+                    &mut reserved,
+                    &mut maps,
+                )
+            }
+            false if *target == player => downhill(&maps.approach_player, monster_pos, |p| {
+                *p != ppos && !occupied.contains(p)
+            })
+            .map(|next| vec![next]),
+            false => {
+                let Ok(target_pos) = mpos.get(*target).copied() else {
+                    continue;
+                };
+                plan_downhill(maps.goal(target_pos), monster_pos, passable)
+            }
+        };
+
+        let Some(new_pos) = plan.as_ref().and_then(|plan| plan.first()).copied() else {
+            trace!(?actor, ?target, "no way closer to the target");
             continue;
         };
+        cmd.entity(*actor)
+            .insert(PlannedPath(plan.unwrap_or_default()));
 
-        // first [Position] should be start
-        match path.get(1) {
-            Some(new_pos) => {
-                mpos.get_mut(*actor)
-                    .map(|mut pos| *pos = *new_pos)
-                    .expect("failed to update position, even tho we got it for path resolution");
-            }
-            None => debug!("path doesn't contain data at index 1"),
+        occupied.remove(&monster_pos);
+        occupied.insert(new_pos);
+        mpos.get_mut(*actor)
+            .map(|mut pos| *pos = new_pos)
+            .expect("failed to update position, even tho we got it before");
+    }
+}
+
+/// Steps of a pack member closing in on `target_pos`, `None` when it waits. Each member heads to its own free tile around the target,
+/// claiming it in `reserved`, and walks there along the goal map of that tile. Members standing in the open wait there
+/// rather than following an ally into a corridor.
+fn pack_step(
+    monster_pos: Position,
+    target_pos: Position,
+    impassable: &HashSet<Position>,
+    occupied: &HashSet<Position>,
+    reserved: &mut HashSet<Position>,
+    maps: &mut DijkstraMaps,
+) -> Option<Vec<Position>> {
+    let open = |p: &Position| *p != target_pos && !impassable.contains(p);
+    let in_corridor =
+        |p: &Position| p.possible_successors().iter().filter(|n| open(n)).count() <= 2;
+
+    let slot = target_pos
+        .possible_successors()
+        .into_iter()
+        .filter(|p| open(p) && !occupied.contains(p) && !reserved.contains(p))
+        .min_by_key(|p| p.distance(monster_pos))?;
+    reserved.insert(slot);
+
+    // allies are walked through when planning, so the monster can tell it would get stuck behind them
+    let path = plan_downhill(maps.goal(slot), monster_pos, open)?;
+    let next = path[0];
+    let behind_ally = path.iter().any(|p| occupied.contains(p));
+
+    match occupied.contains(&next)
+        || (behind_ally && in_corridor(&next) && !in_corridor(&monster_pos))
+    {
+        true => {
+            trace!(?monster_pos, ?slot, "waiting for an ally to make way");
+            None
         }
+        false => Some(path),
+    }
+}
 
-        *action_state = ActionState::Success;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct TargetInAttackRange;
+
+/// Checks whether the [Actor] stands right next to its [Target] and thus can attack it with meelee attack
+/// Sets score of `1.0`
+pub fn target_in_meelee_range_scorer(
+    attackers: Query<(&Position, &Target), With<Monster>>,
+    positions: Query<&Position>,
+    mut score_query: Query<(&Actor, &mut Score), With<TargetInAttackRange>>,
+) {
+    for (Actor(entity), mut score) in score_query.iter_mut() {
+        score.set(
+            attackers
+                .get(*entity)
+                .ok()
+                .and_then(|(pos, Target(target))| {
+                    positions
+                        .get(*target)
+                        .is_ok_and(|target_pos| pos.next_to(target_pos))
+                        .then_some(1.0)
+                })
+                .unwrap_or_default(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct MeeleeAttackTarget;
+
+pub fn meelee_attack_target_action(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<MeeleeAttackTarget>>,
+    attackers: Query<(&Target, Option<&StatusEffects>)>,
+) {
+    for (Actor(entity), mut action_state) in actors.iter_mut() {
+        match *action_state {
+            ActionState::Requested => match attackers.get(*entity) {
+                Ok((_, Some(effects))) if effects.is_stunned() => {
+                    debug!(?entity, "monster is stunned, skipping attack");
+                    *action_state = ActionState::Success;
+                }
+                Ok((Target(target), _)) => {
+                    cmd.entity(*entity)
+                        .insert(MeeleeAttackRequest::new(*target));
+                    *action_state = ActionState::Success;
+                }
+                Err(_) => *action_state = ActionState::Failure,
+            },
+            _ => {
+                warn!("unexpected state in meelee system");
+                *action_state = ActionState::Success;
+            }
+        }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
-pub struct PlayerInAttackRange;
+pub struct TargetInShootingRange;
 
-/// Checks whether the distance from [Actor] to [Player] is `1` and if so, that means [Monster] is standing next to [Player] and thus can attack it with meelee attack
-/// Sets score of `0.1`
-pub fn player_in_meelee_range_scorer(
-    ppos: Query<&Position, With<Player>>,
-    mpos: Query<&Position, (With<Monster>, Without<Player>)>,
-    mut score_query: Query<(&Actor, &mut Score), With<PlayerInAttackRange>>,
+/// Checks whether the [Target] is within the range of [Actor]'s [RangedAttack], there is a clear line of fire and
+/// the [Target] is not standing right next to it, as then meelee attack should be preferred.
+/// Sets score of `0.8`
+pub fn target_in_shooting_range_scorer(
+    shooters: Query<(&Position, &RangedAttack, &Target), With<Monster>>,
+    positions: Query<&Position>,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+    mut score_query: Query<(&Actor, &mut Score), With<TargetInShootingRange>>,
 ) {
-    let ppos = ppos.single();
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
 
     for (Actor(entity), mut score) in score_query.iter_mut() {
         score.set(
-            mpos.get(*entity)
+            shooters
+                .get(*entity)
                 .ok()
-                .map(|pos| {
-                    //debug!(?ppos, ?pos, ?entity, "player vs monster pos");
-                    pos.next_to(ppos).then(|| 1.0)
+                .and_then(|(pos, RangedAttack { range }, Target(target))| {
+                    let target_pos = positions.get(*target).ok()?;
+                    (!pos.next_to(target_pos)
+                        && pos.distance(*target_pos) <= *range
+                        && has_line_of_fire(*pos, *target_pos, |p| blockers.contains(p)))
+                    .then_some(0.8)
                 })
-                .flatten()
                 .unwrap_or_default(),
         );
     }
 }
 
 #[derive(Debug, Clone, Component, ActionBuilder)]
-pub struct MeeleeAttackPlayer;
+pub struct ShootTarget;
 
-pub fn meelee_attack_player_action(
+pub fn shoot_target_action(
     mut cmd: Commands,
-    mut actors: Query<(&Actor, &mut ActionState), With<MeeleeAttackPlayer>>,
-    p_entity: Query<Entity, With<Player>>,
+    mut actors: Query<(&Actor, &mut ActionState), With<ShootTarget>>,
+    shooters: Query<(&RangedAttack, &Target, Option<&StatusEffects>)>,
 ) {
-    let p_entity = p_entity.single();
-
     for (Actor(entity), mut action_state) in actors.iter_mut() {
         match *action_state {
-            ActionState::Requested => {
-                if let Some(mut ent_commands) = cmd.get_entity(*entity) {
-                    ent_commands.insert(MeeleeAttackRequest::new(p_entity));
+            ActionState::Requested => match shooters.get(*entity) {
+                Ok((_, _, Some(effects))) if effects.is_stunned() => {
+                    debug!(?entity, "monster is stunned, skipping shot");
+                    *action_state = ActionState::Success;
+                }
+                Ok((RangedAttack { range }, Target(target), _)) => {
+                    cmd.entity(*entity)
+                        .insert(RangedAttackRequest::new(*target, *range));
                     *action_state = ActionState::Success;
+                }
+                Err(_) => *action_state = ActionState::Failure,
+            },
+            _ => {
+                warn!("unexpected state in shooting system");
+                *action_state = ActionState::Success;
+            }
+        }
+    }
+}
+
+/// Allies are healed once their health drops below this part of the maximum
+const HEAL_HEALTH_RATIO: f32 = 0.6;
+
+/// Casters with their position, faction, sight and current target
+type Casters<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Position,
+        &'static Faction,
+        &'static Viewshed,
+        Option<&'static Target>,
+    ),
+>;
+
+/// Creatures a spell can be cast at
+type SpellTargets<'w, 's> =
+    Query<'w, 's, (Entity, &'static Position, &'static Faction, &'static Health)>;
+
+/// What the `caster` would cast the `spell` at, `None` when there is nothing worth casting it at.
+/// Heal goes to the most wounded ally in sight, bolt at the caster's [Target] and summoned monsters appear on a free
+/// tile next to the caster, but only once there is a [Target] to fight.
+fn spell_target(
+    spell: &Spell,
+    caster: Entity,
+    casters: &Casters,
+    creatures: &SpellTargets,
+    blockers: &HashSet<&Position>,
+) -> Option<SpellTarget> {
+    let (pos, faction, viewshed, target) = casters.get(caster).ok()?;
+    let health_ratio = |health: &Health| health.current as f32 / health.max as f32;
+
+    match spell {
+        Spell::Heal { .. } => creatures
+            .iter()
+            .filter(|(entity, ally_pos, ally_faction, health)| {
+                *entity != caster
+                    && faction.reaction(**ally_faction) == Reaction::Friendly
+                    && viewshed.contains(ally_pos)
+                    && !health.is_dead()
+                    && health_ratio(health) < HEAL_HEALTH_RATIO
+            })
+            .min_by(|(.., a), (.., b)| health_ratio(a).total_cmp(&health_ratio(b)))
+            .map(|(entity, ..)| SpellTarget::Entity(entity)),
+        Spell::Bolt { range, .. } => {
+            let Target(target) = target?;
+            let (_, target_pos, ..) = creatures.get(*target).ok()?;
+            (pos.distance(*target_pos) <= *range
+                && has_line_of_fire(*pos, *target_pos, |p| blockers.contains(p)))
+            .then_some(SpellTarget::Entity(*target))
+        }
+        Spell::Summon { .. } => {
+            target?;
+            let taken = creatures
+                .iter()
+                .map(|(_, pos, ..)| pos)
+                .collect::<HashSet<&Position>>();
+            pos.possible_successors()
+                .into_iter()
+                .find(|p| !blockers.contains(p) && !taken.contains(p))
+                .map(SpellTarget::Position)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct CanCast(pub SpellKind);
+
+/// Checks whether the [Actor] has the spell ready and something worth casting it at.
+/// Sets score of `0.8`
+pub fn can_cast_scorer(
+    spellbooks: Query<&Spellbook>,
+    casters: Casters,
+    creatures: SpellTargets,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+    mut score_query: Query<(&Actor, &mut Score, &CanCast)>,
+) {
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+
+    for (Actor(actor), mut score, CanCast(kind)) in score_query.iter_mut() {
+        score.set(
+            spellbooks
+                .get(*actor)
+                .ok()
+                .and_then(|spellbook| spellbook.ready(*kind))
+                .and_then(|spell| spell_target(&spell, *actor, &casters, &creatures, &blockers))
+                .map(|_| 0.8)
+                .unwrap_or_default(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, ActionBuilder)]
+pub struct CastSpell(pub SpellKind);
+
+/// Requests casting the spell and puts it on cooldown
+pub fn cast_spell_action(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState, &CastSpell)>,
+    mut spellbooks: Query<&mut Spellbook>,
+    casters: Casters,
+    creatures: SpellTargets,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+    status_effects: Query<&StatusEffects>,
+) {
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+
+    for (Actor(actor), mut action_state, CastSpell(kind)) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        if status_effects
+            .get(*actor)
+            .is_ok_and(StatusEffects::is_stunned)
+        {
+            debug!(?actor, "monster is stunned, skipping spell");
+            continue;
+        }
+
+        let Ok(mut spellbook) = spellbooks.get_mut(*actor) else {
+            continue;
+        };
+        let Some((spell, target)) = spellbook.ready(*kind).and_then(|spell| {
+            spell_target(&spell, *actor, &casters, &creatures, &blockers)
+                .map(|target| (spell, target))
+        }) else {
+            continue;
+        };
+
+        debug!(?actor, ?spell, ?target, "casting spell");
+        spellbook.start_cooldown(*kind);
+        cmd.entity(*actor)
+            .insert(CastSpellRequest::new(spell, target));
+    }
+}
+
+/// Monsters start thinking about fleeing once their health drops below this part of the maximum
+const FLEE_HEALTH_RATIO: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct LowHealth;
+
+/// Scores monsters with health below [FLEE_HEALTH_RATIO] of their maximum, the more wounded they are, the higher
+/// the score. Healthy monsters score `0`.
+pub fn low_health_scorer(
+    health: Query<&Health, With<Monster>>,
+    mut score_query: Query<(&Actor, &mut Score), With<LowHealth>>,
+) {
+    for (Actor(entity), mut score) in score_query.iter_mut() {
+        score.set(
+            health
+                .get(*entity)
+                .ok()
+                .map(|health| health.current as f32 / health.max as f32)
+                .filter(|ratio| *ratio < FLEE_HEALTH_RATIO)
+                .map(|ratio| (1.0 - ratio).clamp(0.0, 1.0))
+                .unwrap_or_default(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct FleeFromPlayer;
+
+/// Moves the monster downhill on the shared flee map, which prefers routes leading far away from the player over corners
+/// that are only the furthest spot nearby. Cornered monster with nowhere to go fights back.
+pub fn flee_from_player(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<FleeFromPlayer>>,
+    maps: Res<DijkstraMaps>,
+    mut mpos: Query<&mut Position, (With<Monster>, Without<BlocksTile>)>,
+    player: PlayerPosition,
+    status_effects: Query<&StatusEffects>,
+    mut barks: EventWriter<CurseAtPlayer>,
+) {
+    let (player_ent, player_pos) = player.single();
+    let mut occupied = mpos.iter().copied().collect::<HashSet<Position>>();
+
+    for (Actor(actor), mut action_state) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        if status_effects
+            .get(*actor)
+            .is_ok_and(StatusEffects::is_stunned)
+        {
+            debug!(?actor, "monster is stunned, skipping turn");
+            continue;
+        }
+
+        let Ok(monster_pos) = mpos.get(*actor).copied() else {
+            continue;
+        };
+        let escape = downhill(&maps.flee_player, monster_pos, |p| {
+            p != player_pos && !occupied.contains(p)
+        });
+
+        match escape {
+            Some(new_pos) => {
+                debug!(?actor, ?new_pos, "fleeing from player");
+                barks.send(CurseAtPlayer {
+                    monster: *actor,
+                    trigger: BarkTrigger::Fleeing,
+                });
+                occupied.remove(&monster_pos);
+                occupied.insert(new_pos);
+                if let Ok(mut pos) = mpos.get_mut(*actor) {
+                    *pos = new_pos;
+                }
+            }
+            None if monster_pos.next_to(player_pos) => {
+                debug!(?actor, "cornered, fighting back");
+                cmd.entity(*actor)
+                    .insert(MeeleeAttackRequest::new(player_ent));
+            }
+            None => debug!(?actor, "nowhere to flee"),
+        }
+    }
+}
+
+/// How close the player has to be for a sleeping monster to have a chance of waking up
+const WAKE_DISTANCE: i32 = 5;
+
+/// How far from its current position a wandering monster picks its next goal
+const WANDER_RANGE: i32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct Asleep;
+
+/// Sleeping monsters score `1`, so sleeping always wins over anything else
+pub fn asleep_scorer(
+    sleeping: Query<(), With<Sleeping>>,
+    mut score_query: Query<(&Actor, &mut Score), With<Asleep>>,
+) {
+    for (Actor(entity), mut score) in score_query.iter_mut() {
+        score.set(match sleeping.contains(*entity) {
+            true => 1.0,
+            false => 0.0,
+        });
+    }
+}
+
+/// Chance that a sleeping monster notices player with given `stealth` standing nearby
+fn wake_chance(stealth: i32) -> f64 {
+    (0.5 - 0.1 * stealth as f64).clamp(0.05, 1.0)
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct Sleep;
+
+/// Sleeping monster stays still. It wakes up once hurt, or with a chance based on player's [Stealth] when the
+/// player comes close.
+pub fn sleep_action(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<Sleep>>,
+    monsters: Query<(&Position, &Health), With<Monster>>,
+    player: Query<(&Position, Option<&Stealth>), With<Player>>,
+) {
+    let (ppos, stealth) = player.single();
+    let stealth = stealth.map(|Stealth(stealth)| *stealth).unwrap_or_default();
+
+    for (Actor(actor), mut action_state) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        let Ok((pos, health)) = monsters.get(*actor) else {
+            continue;
+        };
+
+        let hurt = health.current < health.max;
+        let noticed = pos.distance(*ppos) <= WAKE_DISTANCE
+            && rand::thread_rng().gen_bool(wake_chance(stealth));
+        if hurt || noticed {
+            debug!(?actor, %hurt, "monster wakes up");
+            cmd.entity(*actor).remove::<Sleeping>();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct Wander;
+
+/// Monster with nothing better to do walks towards a random reachable floor tile nearby, picking a new one once
+/// it gets there
+pub fn wander_action(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<Wander>>,
+    mut maps: ResMut<DijkstraMaps>,
+    floors: Query<&Position, (With<Floor>, Without<Monster>)>,
+    mut mpos: Query<&mut Position, With<Monster>>,
+    ppos: Query<&Position, (With<Player>, Without<Monster>)>,
+    wanderers: Query<(Option<&StatusEffects>, Option<&WanderGoal>)>,
+) {
+    let ppos = *ppos.single();
+    let mut rng = rand::thread_rng();
+    // kept up to date as monsters move, so they do not step onto each other
+    let mut occupied = mpos.iter().copied().collect::<HashSet<Position>>();
+
+    for (Actor(actor), mut action_state) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        let Ok((effects, goal)) = wanderers.get(*actor) else {
+            continue;
+        };
+        if effects.is_some_and(StatusEffects::is_stunned) {
+            continue;
+        }
+
+        let Ok(monster_pos) = mpos.get(*actor).copied() else {
+            continue;
+        };
+
+        let goal = match goal {
+            Some(WanderGoal(goal)) if *goal != monster_pos => *goal,
+            _ => {
+                let nearby = floors
+                    .iter()
+                    .filter(|pos| pos.distance(monster_pos) <= WANDER_RANGE)
+                    .collect::<Vec<_>>();
+                let Some(goal) = nearby.get(rng.gen_range(0..nearby.len().max(1))) else {
                     continue;
+                };
+                **goal
+            }
+        };
+
+        let goal_map = maps.goal(goal);
+        if !goal_map.contains_key(&monster_pos) {
+            // goal is unreachable, next time another one is picked
+            cmd.entity(*actor).remove::<WanderGoal>();
+            continue;
+        }
+        let Some(plan) = plan_downhill(goal_map, monster_pos, |p| {
+            *p != ppos && !occupied.contains(p)
+        }) else {
+            // someone is in the way, the monster waits for it to move
+            cmd.entity(*actor).insert(WanderGoal(goal));
+            continue;
+        };
+
+        trace!(?actor, ?goal, "wandering");
+        occupied.remove(&monster_pos);
+        occupied.insert(plan[0]);
+        if let Ok(mut pos) = mpos.get_mut(*actor) {
+            *pos = plan[0];
+        }
+        cmd.entity(*actor)
+            .insert((WanderGoal(goal), PlannedPath(plan)));
+    }
+}
+
+/// Awake monsters hostile to the player that see it remember where, the others slowly forget. Sleeping monsters
+/// notice the player only once they wake up.
+pub fn remember_player(
+    mut cmd: Commands,
+    mut monsters: Query<(Entity, &Faction, &Viewshed, Option<&mut PlayerMemory>), With<Monster>>,
+    sleeping: Query<(), With<Sleeping>>,
+    player: Query<(&Position, &Faction), With<Player>>,
+    mut barks: EventWriter<CurseAtPlayer>,
+) {
+    let (ppos, player_faction) = player.single();
+
+    for (monster, faction, viewshed, memory) in monsters.iter_mut() {
+        if !faction.is_hostile_to(*player_faction) || sleeping.contains(monster) {
+            continue;
+        }
+
+        match memory {
+            _ if viewshed.contains(ppos) => {
+                if memory.is_none() {
+                    barks.send(CurseAtPlayer {
+                        monster,
+                        trigger: BarkTrigger::Spotted,
+                    });
                 }
+                cmd.entity(monster).insert(PlayerMemory::new(*ppos));
+            }
+            Some(mut memory) if memory.turns_left > 1 => memory.turns_left -= 1,
+            Some(_) => {
+                trace!(?monster, "forgot about the player");
+                cmd.entity(monster).remove::<PlayerMemory>();
+            }
+            None => (),
+        }
+    }
+}
+
+/// How far pack members hear each other calling out the player
+const ALERT_RANGE: i32 = 8;
 
-                *action_state = ActionState::Failure;
+/// Awake pack members, the ones able to call out the player
+type PackSpotters<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Faction, &'static Viewshed, &'static Position),
+    (With<Pack>, Without<Sleeping>),
+>;
+
+/// Awake [Pack] members that see the player alert their allies nearby. Alerted allies wake up and head to where
+/// the player was spotted.
+pub fn alert_pack(
+    mut cmd: Commands,
+    spotters: PackSpotters,
+    allies: Query<(Entity, &Faction, &Viewshed, &Position), With<Monster>>,
+    ppos: Query<&Position, With<Player>>,
+) {
+    let ppos = ppos.single();
+    let spotters = spotters
+        .iter()
+        .filter(|(_, viewshed, _)| viewshed.contains(ppos))
+        .collect::<Vec<_>>();
+
+    for (ally, faction, viewshed, pos) in allies.iter() {
+        let alerted = !viewshed.contains(ppos)
+            && spotters.iter().any(|(spotter_faction, _, spotter_pos)| {
+                faction.reaction(**spotter_faction) == Reaction::Friendly
+                    && pos.distance(**spotter_pos) <= ALERT_RANGE
+            });
+
+        if alerted {
+            trace!(?ally, "alerted by the pack");
+            cmd.entity(ally)
+                .remove::<Sleeping>()
+                .insert(PlayerMemory::new(*ppos));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct RemembersPlayer;
+
+/// Monster which does not see the player, but still remembers where it saw it the last time.
+/// Sets score of `0.55`
+pub fn remembers_player_scorer(
+    monsters: Query<(&Viewshed, Option<&PlayerMemory>), With<Monster>>,
+    ppos: Query<&Position, With<Player>>,
+    mut score_query: Query<(&Actor, &mut Score), With<RemembersPlayer>>,
+) {
+    let ppos = ppos.single();
+
+    for (Actor(entity), mut score) in score_query.iter_mut() {
+        score.set(match monsters.get(*entity) {
+            Ok((viewshed, Some(_))) if !viewshed.contains(ppos) => 0.55,
+            _ => 0.0,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct SearchForPlayer;
+
+/// Monster goes to the tile where it last saw the player. Once there, it searches around by stepping to random
+/// neighbouring tiles until it finds the player again or forgets about it.
+pub fn search_for_player(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<SearchForPlayer>>,
+    mut maps: ResMut<DijkstraMaps>,
+    blockers: Query<&Position, (With<BlocksTile>, Without<Monster>)>,
+    mut mpos: Query<&mut Position, (With<Monster>, Without<BlocksTile>)>,
+    ppos: Query<&Position, (With<Player>, Without<Monster>)>,
+    searchers: Query<(Option<&StatusEffects>, &PlayerMemory)>,
+) {
+    let impassable = blockers.iter().copied().collect::<HashSet<Position>>();
+    let ppos = *ppos.single();
+    // kept up to date as monsters move, so they do not step onto each other
+    let mut occupied = mpos.iter().copied().collect::<HashSet<Position>>();
+
+    for (Actor(actor), mut action_state) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        let (Ok(monster_pos), Ok((effects, memory))) =
+            (mpos.get(*actor).copied(), searchers.get(*actor))
+        else {
+            continue;
+        };
+        if effects.is_some_and(StatusEffects::is_stunned) {
+            continue;
+        }
+
+        let passable =
+            |p: &Position| *p != ppos && !impassable.contains(p) && !occupied.contains(p);
+
+        let plan = match monster_pos == memory.last_seen {
+            true => {
+                let around = monster_pos
+                    .possible_successors()
+                    .into_iter()
+                    .filter(passable)
+                    .collect::<Vec<_>>();
+                around
+                    .get(rand::thread_rng().gen_range(0..around.len().max(1)))
+                    .map(|next| vec![*next])
+            }
+            false => plan_downhill(maps.goal(memory.last_seen), monster_pos, passable),
+        };
+
+        if let (Some(next), Ok(mut pos)) = (
+            plan.as_ref().and_then(|plan| plan.first()).copied(),
+            mpos.get_mut(*actor),
+        ) {
+            trace!(?actor, last_seen = ?memory.last_seen, "searching for player");
+            occupied.remove(&monster_pos);
+            occupied.insert(next);
+            *pos = next;
+            cmd.entity(*actor)
+                .insert(PlannedPath(plan.unwrap_or_default()));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScorerBuilder, Component)]
+pub struct HeardNoise;
+
+/// Monster heard a noise it has not investigated yet.
+/// Sets score of `0.52`
+pub fn heard_noise_scorer(
+    listeners: Query<Has<HeardSound>, With<Monster>>,
+    mut score_query: Query<(&Actor, &mut Score), With<HeardNoise>>,
+) {
+    for (Actor(entity), mut score) in score_query.iter_mut() {
+        score.set(match listeners.get(*entity) {
+            Ok(true) => 0.52,
+            _ => 0.0,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Component, ActionBuilder)]
+pub struct InvestigateNoise;
+
+/// Monster walks to where it heard the noise and forgets about it once there or when there is no way to get there
+pub fn investigate_noise(
+    mut cmd: Commands,
+    mut actors: Query<(&Actor, &mut ActionState), With<InvestigateNoise>>,
+    mut maps: ResMut<DijkstraMaps>,
+    mut mpos: Query<&mut Position, With<Monster>>,
+    ppos: Query<&Position, (With<Player>, Without<Monster>)>,
+    listeners: Query<(Option<&StatusEffects>, &HeardSound)>,
+) {
+    let ppos = *ppos.single();
+    // kept up to date as monsters move, so they do not step onto each other
+    let mut occupied = mpos.iter().copied().collect::<HashSet<Position>>();
+
+    for (Actor(actor), mut action_state) in actors.iter_mut() {
+        if !matches!(*action_state, ActionState::Requested) {
+            warn!(?action_state, "unexpected action state");
+            *action_state = ActionState::Success;
+            continue;
+        };
+        *action_state = ActionState::Success;
+
+        let (Ok(monster_pos), Ok((effects, HeardSound(origin)))) =
+            (mpos.get(*actor).copied(), listeners.get(*actor))
+        else {
+            continue;
+        };
+        if effects.is_some_and(StatusEffects::is_stunned) {
+            continue;
+        }
+
+        // someone may be standing right at the origin, getting next to it is enough
+        let plan = match monster_pos == *origin || monster_pos.next_to(origin) {
+            true => None,
+            false => plan_downhill(maps.goal(*origin), monster_pos, |p| {
+                *p != ppos && !occupied.contains(p)
+            }),
+        };
+
+        match (plan, mpos.get_mut(*actor)) {
+            (Some(plan), Ok(mut pos)) => {
+                trace!(?actor, ?origin, "investigating noise");
+                occupied.remove(&monster_pos);
+                occupied.insert(plan[0]);
+                *pos = plan[0];
+                cmd.entity(*actor).insert(PlannedPath(plan));
             }
             _ => {
-                warn!("unexpected state in meelee system");
-                *action_state = ActionState::Success;
+                trace!(?actor, ?origin, "done investigating noise");
+                cmd.entity(*actor).remove::<HeardSound>();
             }
         }
     }
@@ -344,6 +344,205 @@ pub mod fov {
         }
     }
 
+    /// Algorithm used to compute a field of view
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub enum FovMode {
+        /// [MyVisibility], sees around corners generously but is not symmetric
+        #[default]
+        Permissive,
+        /// [SymmetricShadowcasting], a floor tile sees another floor tile exactly when it is seen by it
+        Symmetric,
+        /// [Raycasting] to the edge of the range, cheap and crude
+        Raycast,
+    }
+
+    impl FovMode {
+        /// Computes tiles visible from `origin` using this mode. Arguments have the same meaning as for
+        /// [MyVisibility].
+        pub fn compute(
+            &self,
+            origin: Position,
+            range_limit: i32,
+            blocks_light: impl Fn(i32, i32) -> bool,
+            get_distance: impl Fn(i32, i32) -> i32,
+        ) -> HashSet<Position> {
+            match self {
+                FovMode::Permissive => {
+                    MyVisibility::new(blocks_light, get_distance).compute(origin, range_limit)
+                }
+                FovMode::Symmetric => SymmetricShadowcasting::new(blocks_light, get_distance)
+                    .compute(origin, range_limit),
+                FovMode::Raycast => {
+                    Raycasting::new(blocks_light, get_distance).compute(origin, range_limit)
+                }
+            }
+        }
+    }
+
+    /// Symmetric shadowcasting as described at <https://www.albertford.com/shadowcasting/>.
+    /// A floor tile is visible only if there is an unobstructed line from the center of the origin to its center,
+    /// which makes the result symmetric. Walls are visible if any part of them is lit.
+    pub struct SymmetricShadowcasting<F, H>
+    where
+        F: Fn(i32, i32) -> bool,
+        H: Fn(i32, i32) -> i32,
+    {
+        blocks_light: F,
+        get_distance: H,
+    }
+
+    /// Row of tiles in a quadrant `depth` tiles away from the origin, lit between the `start` and `end` slopes.
+    /// Slopes are stored as a fraction (numerator, denominator) with a positive denominator.
+    #[derive(Debug, Clone, Copy)]
+    struct Row {
+        depth: i32,
+        start: (i32, i32),
+        end: (i32, i32),
+    }
+
+    impl Row {
+        /// `depth * start` rounded with ties going up
+        fn min_col(&self) -> i32 {
+            (2 * self.depth * self.start.0 + self.start.1).div_euclid(2 * self.start.1)
+        }
+
+        /// `depth * end` rounded with ties going down
+        fn max_col(&self) -> i32 {
+            -(self.end.1 - 2 * self.depth * self.end.0).div_euclid(2 * self.end.1)
+        }
+
+        /// Whether the center of the tile in `col` lies between the start and end slopes
+        fn is_symmetric(&self, col: i32) -> bool {
+            col * self.start.1 >= self.depth * self.start.0
+                && col * self.end.1 <= self.depth * self.end.0
+        }
+
+        fn next(&self) -> Self {
+            Self {
+                depth: self.depth + 1,
+                ..*self
+            }
+        }
+    }
+
+    /// Slope of the line from the origin to the left edge of the tile in `col`
+    fn slope(depth: i32, col: i32) -> (i32, i32) {
+        (2 * col - 1, 2 * depth)
+    }
+
+    impl<F, H> SymmetricShadowcasting<F, H>
+    where
+        F: Fn(i32, i32) -> bool,
+        H: Fn(i32, i32) -> i32,
+    {
+        pub fn new(blocks_light: F, get_distance: H) -> Self {
+            Self {
+                blocks_light,
+                get_distance,
+            }
+        }
+
+        pub fn compute(&self, origin: Position, range_limit: i32) -> HashSet<Position> {
+            let mut visible_pos = HashSet::new();
+            visible_pos.insert(origin);
+
+            for quadrant in 0..4 {
+                let translate = |depth: i32, col: i32| match quadrant {
+                    0 => (origin.x + col, origin.y - depth),
+                    1 => (origin.x + depth, origin.y + col),
+                    2 => (origin.x + col, origin.y + depth),
+                    3 => (origin.x - depth, origin.y + col),
+                    _ => unreachable!(),
+                };
+                let mut rows = vec![Row {
+                    depth: 1,
+                    start: (-1, 1),
+                    end: (1, 1),
+                }];
+
+                while let Some(mut row) = rows.pop() {
+                    let mut was_opaque = None;
+                    let has_next = range_limit < 0 || row.depth < range_limit;
+
+                    for col in row.min_col()..=row.max_col() {
+                        let (x, y) = translate(row.depth, col);
+                        let is_opaque = (self.blocks_light)(x, y);
+                        let in_range = range_limit < 0
+                            || (self.get_distance)(row.depth, col.abs()) <= range_limit;
+
+                        if in_range && (is_opaque || row.is_symmetric(col)) {
+                            visible_pos.insert(Position::new(x, y, origin.z));
+                        }
+                        match (was_opaque, is_opaque) {
+                            // light starts again right after the wall
+                            (Some(true), false) => row.start = slope(row.depth, col),
+                            // the wall casts a shadow, the lit part before it goes on in the next row
+                            (Some(false), true) if has_next => rows.push(Row {
+                                end: slope(row.depth, col),
+                                ..row.next()
+                            }),
+                            _ => (),
+                        }
+                        was_opaque = Some(is_opaque);
+                    }
+
+                    if was_opaque == Some(false) && has_next {
+                        rows.push(row.next());
+                    }
+                }
+            }
+            visible_pos
+        }
+    }
+
+    /// Casts a Bresenham line from the origin to every tile on the edge of the range. Tiles along each line are
+    /// visible up to and including the first one blocking light.
+    pub struct Raycasting<F, H>
+    where
+        F: Fn(i32, i32) -> bool,
+        H: Fn(i32, i32) -> i32,
+    {
+        blocks_light: F,
+        get_distance: H,
+    }
+
+    impl<F, H> Raycasting<F, H>
+    where
+        F: Fn(i32, i32) -> bool,
+        H: Fn(i32, i32) -> i32,
+    {
+        pub fn new(blocks_light: F, get_distance: H) -> Self {
+            Self {
+                blocks_light,
+                get_distance,
+            }
+        }
+
+        /// Unlike the shadowcasters, raycasting needs a non-negative `range_limit` to know where to cast the rays
+        pub fn compute(&self, origin: Position, range_limit: i32) -> HashSet<Position> {
+            let mut visible_pos = HashSet::new();
+            visible_pos.insert(origin);
+            let range = range_limit.max(0);
+
+            let edge =
+                (-range..=range).flat_map(|i| [(i, -range), (i, range), (-range, i), (range, i)]);
+            for (dx, dy) in edge {
+                let end = Position::new(origin.x + dx, origin.y + dy, origin.z);
+                for pos in super::line::line(origin, end).into_iter().skip(1) {
+                    let (dx, dy) = ((pos.x - origin.x).abs(), (pos.y - origin.y).abs());
+                    if (self.get_distance)(dx.max(dy), dx.min(dy)) > range {
+                        break;
+                    }
+                    visible_pos.insert(pos);
+                    if (self.blocks_light)(pos.x, pos.y) {
+                        break;
+                    }
+                }
+            }
+            visible_pos
+        }
+    }
+
     // fn main() {
     //     let origin = LevelPoint { x: 5, y: 5 };
     //     let visibility = MyVisibility::new(
@@ -353,4 +552,465 @@ pub mod fov {
     //     );
     //     visibility.compute(origin, 10);
     // }
+
+    #[cfg(test)]
+    pub(crate) mod tests {
+        use super::*;
+
+        /// Fixture maps, `#` is a wall and `.` is a floor. Anything outside the map is a wall.
+        const MAPS: [&str; 4] = [
+            "\
+##########
+#........#
+#..#.....#
+#........#
+#.....#..#
+#........#
+##########",
+            "\
+###########
+#....#....#
+#.........#
+####.#.####
+#.........#
+#....#....#
+###########",
+            "\
+############
+#..#...#...#
+#.#..#...#.#
+#...#..#...#
+#.#....#.#.#
+#...#.#....#
+############",
+            "\
+#########
+#.#.#.#.#
+#.......#
+#.#.#.#.#
+#.......#
+#.#.#.#.#
+#########",
+        ];
+
+        const RANGE: i32 = 8;
+
+        /// Floor tiles of the `map`, `#` is a wall and `.` is a floor
+        pub(crate) fn parse(map: &str) -> HashSet<Position> {
+            map.lines()
+                .enumerate()
+                .flat_map(|(y, line)| {
+                    line.chars()
+                        .enumerate()
+                        .filter(|(_, tile)| *tile == '.')
+                        .map(move |(x, _)| Position::new(x as i32, y as i32, 0))
+                })
+                .collect()
+        }
+
+        fn fov(mode: FovMode, floor: &HashSet<Position>, origin: Position) -> HashSet<Position> {
+            mode.compute(
+                origin,
+                RANGE,
+                |x, y| !floor.contains(&Position::new(x, y, 0)),
+                |x, y| ((x * x + y * y) as f64).sqrt() as i32,
+            )
+        }
+
+        /// Pairs of floor tiles where only one of them sees the other
+        fn asymmetric_pairs(mode: FovMode, map: &str) -> Vec<(Position, Position)> {
+            let floor = parse(map);
+            let views = floor
+                .iter()
+                .map(|pos| (*pos, fov(mode, &floor, *pos)))
+                .collect::<Vec<_>>();
+
+            views
+                .iter()
+                .flat_map(|(a, seen_by_a)| {
+                    seen_by_a
+                        .iter()
+                        .filter(|b| floor.contains(*b))
+                        .filter(|b| {
+                            let (_, seen_by_b) = views.iter().find(|(pos, _)| pos == *b).unwrap();
+                            !seen_by_b.contains(a)
+                        })
+                        .map(|b| (*a, *b))
+                })
+                .collect()
+        }
+
+        #[test]
+        fn symmetric_mode_is_symmetric() {
+            for map in MAPS {
+                let pairs = asymmetric_pairs(FovMode::Symmetric, map);
+                assert!(pairs.is_empty(), "asymmetric pairs {pairs:?} in\n{map}");
+            }
+        }
+
+        #[test]
+        fn every_mode_sees_whole_open_room() {
+            let floor = parse(MAPS[0]);
+            let origin = Position::new(1, 1, 0);
+            for mode in [FovMode::Permissive, FovMode::Symmetric, FovMode::Raycast] {
+                let visible = fov(mode, &floor, origin);
+                assert!(visible.contains(&origin), "{mode:?}");
+                assert!(visible.contains(&Position::new(8, 1, 0)), "{mode:?}");
+                assert!(visible.contains(&Position::new(1, 5, 0)), "{mode:?}");
+                assert!(
+                    visible.contains(&Position::new(0, 0, 0)),
+                    "{mode:?} sees the walls"
+                );
+            }
+        }
+
+        #[test]
+        fn every_mode_is_blocked_by_walls() {
+            let floor = parse(MAPS[1]);
+            let origin = Position::new(1, 1, 0);
+            for mode in [FovMode::Permissive, FovMode::Symmetric, FovMode::Raycast] {
+                let visible = fov(mode, &floor, origin);
+                assert!(visible.contains(&Position::new(5, 1, 0)), "{mode:?}");
+                assert!(!visible.contains(&Position::new(6, 1, 0)), "{mode:?}");
+                assert!(!visible.contains(&Position::new(1, 4, 0)), "{mode:?}");
+            }
+        }
+
+        #[test]
+        fn every_mode_respects_range() {
+            let floor = (-20..=20)
+                .flat_map(|x| (-20..=20).map(move |y| Position::new(x, y, 0)))
+                .collect();
+            let origin = Position::new(0, 0, 0);
+            for mode in [FovMode::Permissive, FovMode::Symmetric, FovMode::Raycast] {
+                let visible = fov(mode, &floor, origin);
+                assert!(visible.contains(&Position::new(RANGE, 0, 0)), "{mode:?}");
+                assert!(
+                    !visible.contains(&Position::new(RANGE + 1, 0, 0)),
+                    "{mode:?}"
+                );
+                assert!(
+                    !visible.contains(&Position::new(RANGE, RANGE, 0)),
+                    "{mode:?}"
+                );
+            }
+        }
+    }
+}
+
+pub mod line {
+    //! Bresenham's line algorithm, used for line of fire checks
+    use crate::components::Position;
+
+    /// Returns all positions on the line from `start` to `end`, both ends included
+    pub fn line(start: Position, end: Position) -> Vec<Position> {
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
+        let step_x = if start.x < end.x { 1 } else { -1 };
+        let step_y = if start.y < end.y { 1 } else { -1 };
+
+        let mut error = dx + dy;
+        let (mut x, mut y) = (start.x, start.y);
+        let mut positions = vec![];
+
+        loop {
+            positions.push(Position::new(x, y, start.z));
+            if x == end.x && y == end.y {
+                break;
+            }
+
+            let doubled_error = 2 * error;
+            if doubled_error >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled_error <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+
+        positions
+    }
+
+    /// Checks whether there is nothing blocking the line between `start` and `end`. The `start` and `end` positions
+    /// themselves are not checked, since those are occupied by the shooter and its target.
+    pub fn has_line_of_fire(
+        start: Position,
+        end: Position,
+        blocks: impl Fn(&Position) -> bool,
+    ) -> bool {
+        let line = line(start, end);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|pos| !blocks(pos))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// One end point in each octant around the origin, plus straight and diagonal lines
+        const ENDS: [(i32, i32); 16] = [
+            (5, 2),
+            (2, 5),
+            (-2, 5),
+            (-5, 2),
+            (-5, -2),
+            (-2, -5),
+            (2, -5),
+            (5, -2),
+            (4, 0),
+            (0, 4),
+            (-4, 0),
+            (0, -4),
+            (3, 3),
+            (-3, 3),
+            (-3, -3),
+            (3, -3),
+        ];
+
+        #[test]
+        fn line_includes_both_ends() {
+            let start = Position::new(0, 0, 0);
+            for (x, y) in ENDS {
+                let end = Position::new(x, y, 0);
+                let line = line(start, end);
+                assert_eq!(line.first(), Some(&start), "{line:?}");
+                assert_eq!(line.last(), Some(&end), "{line:?}");
+            }
+        }
+
+        #[test]
+        fn line_takes_one_step_at_a_time() {
+            let start = Position::new(0, 0, 0);
+            for (x, y) in ENDS {
+                let line = line(start, Position::new(x, y, 0));
+                assert_eq!(
+                    line.len() as i32,
+                    i32::max(x.abs(), y.abs()) + 1,
+                    "{line:?}"
+                );
+                for step in line.windows(2) {
+                    let (dx, dy) = (step[1].x - step[0].x, step[1].y - step[0].y);
+                    assert!(dx.abs() <= 1 && dy.abs() <= 1, "{line:?}");
+                    assert!(dx * x >= 0 && dy * y >= 0, "{line:?} goes backwards");
+                }
+            }
+        }
+
+        #[test]
+        fn line_to_itself_is_single_position() {
+            let pos = Position::new(3, -2, 0);
+            assert_eq!(line(pos, pos), vec![pos]);
+        }
+
+        #[test]
+        fn blocker_between_blocks_line_of_fire() {
+            let start = Position::new(0, 0, 0);
+            let end = Position::new(4, 0, 0);
+            let blocker = Position::new(2, 0, 0);
+            assert!(!has_line_of_fire(start, end, |pos| *pos == blocker));
+            assert!(has_line_of_fire(start, end, |_| false));
+        }
+
+        #[test]
+        fn ends_do_not_block_line_of_fire() {
+            let start = Position::new(0, 0, 0);
+            let end = Position::new(5, 2, 0);
+            assert!(has_line_of_fire(start, end, |pos| *pos == end));
+            assert!(has_line_of_fire(start, end, |pos| *pos == start));
+            assert!(has_line_of_fire(start, Position::new(1, 1, 0), |_| true));
+        }
+    }
+}
+
+pub mod dijkstra {
+    //! Dijkstra maps, distances from a set of goals to every reachable tile. Monsters follow them downhill.
+    use crate::components::Position;
+    use bevy::utils::HashMap;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    /// Distance from the closest of `sources` to every tile reachable within `max_distance` steps, not going
+    /// through tiles for which `blocks` returns `true`
+    pub fn dijkstra_map(
+        sources: impl IntoIterator<Item = Position>,
+        max_distance: i32,
+        blocks: impl Fn(&Position) -> bool,
+    ) -> HashMap<Position, i32> {
+        relax(
+            sources.into_iter().map(|pos| (pos, 0)).collect(),
+            |pos, distance| distance <= max_distance && !blocks(pos),
+        )
+    }
+
+    /// Map leading away from the `sources`. Distances of the approach map are inverted and scaled, then the map is
+    /// rescanned, so following it prefers escape routes leading further away over dead ends that are merely
+    /// the furthest spot nearby.
+    pub fn flee_map(
+        sources: impl IntoIterator<Item = Position>,
+        max_distance: i32,
+        blocks: impl Fn(&Position) -> bool,
+    ) -> HashMap<Position, i32> {
+        let approach = dijkstra_map(sources, max_distance, blocks);
+        let inverted = approach
+            .iter()
+            .map(|(pos, distance)| (*pos, -distance * 12 / 10))
+            .collect::<HashMap<Position, i32>>();
+        // rescan stays within the area covered by the approach map
+        relax(inverted, |pos, _| approach.contains_key(pos))
+    }
+
+    /// Neighbour of `from` with the lowest value in the `map`, as long as it is lower than the value of `from`.
+    /// Only neighbours accepted by `free` are considered.
+    pub fn downhill(
+        map: &HashMap<Position, i32>,
+        from: Position,
+        free: impl Fn(&Position) -> bool,
+    ) -> Option<Position> {
+        let current = map.get(&from).copied().unwrap_or(i32::MAX);
+        from.possible_successors()
+            .into_iter()
+            .filter(|pos| free(pos))
+            .filter_map(|pos| map.get(&pos).map(|value| (pos, *value)))
+            .filter(|(_, value)| *value < current)
+            .min_by_key(|(_, value)| *value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Lowers values of tiles whose neighbour has value smaller by more than one, until no such tile is left.
+    /// Tile is only given a value if `allowed` accepts it.
+    fn relax(
+        initial: HashMap<Position, i32>,
+        allowed: impl Fn(&Position, i32) -> bool,
+    ) -> HashMap<Position, i32> {
+        let mut values = initial;
+        let mut queue = values
+            .iter()
+            .map(|(pos, value)| Reverse((*value, pos.x, pos.y)))
+            .collect::<BinaryHeap<_>>();
+
+        while let Some(Reverse((value, x, y))) = queue.pop() {
+            let pos = Position::new(x, y, 0);
+            if values.get(&pos).is_some_and(|current| *current < value) {
+                continue;
+            }
+
+            for next in pos.possible_successors() {
+                let next_value = value + 1;
+                if !allowed(&next, next_value) {
+                    continue;
+                }
+                if values
+                    .get(&next)
+                    .is_some_and(|current| *current <= next_value)
+                {
+                    continue;
+                }
+                values.insert(next, next_value);
+                queue.push(Reverse((next_value, next.x, next.y)));
+            }
+        }
+
+        values
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::algorithms::fov::tests::parse;
+
+        /// The wall in the middle has to be walked around
+        const MAP: &str = "\
+#######
+#..#..#
+#..#..#
+#.....#
+#######";
+
+        fn map(max_distance: i32) -> HashMap<Position, i32> {
+            let floor = parse(MAP);
+            dijkstra_map([Position::new(1, 1, 0)], max_distance, |pos| {
+                !floor.contains(pos)
+            })
+        }
+
+        #[test]
+        fn distances_go_around_walls() {
+            let map = map(20);
+            assert_eq!(map.get(&Position::new(1, 1, 0)), Some(&0));
+            assert_eq!(map.get(&Position::new(2, 1, 0)), Some(&1));
+            assert_eq!(map.get(&Position::new(2, 2, 0)), Some(&1));
+            assert_eq!(map.get(&Position::new(3, 3, 0)), Some(&2));
+            assert_eq!(map.get(&Position::new(4, 2, 0)), Some(&3));
+            assert_eq!(map.get(&Position::new(4, 1, 0)), Some(&4));
+            assert_eq!(map.get(&Position::new(5, 1, 0)), Some(&4));
+            assert_eq!(map.get(&Position::new(3, 1, 0)), None, "wall");
+            assert_eq!(map.get(&Position::new(0, 0, 0)), None, "wall");
+        }
+
+        #[test]
+        fn distances_stop_at_max_distance() {
+            let map = map(3);
+            assert_eq!(map.get(&Position::new(4, 2, 0)), Some(&3));
+            assert_eq!(map.get(&Position::new(4, 1, 0)), None);
+            assert!(map.values().all(|distance| *distance <= 3));
+        }
+
+        #[test]
+        fn downhill_leads_to_the_source() {
+            let map = map(20);
+            assert_eq!(
+                downhill(&map, Position::new(4, 1, 0), |_| true),
+                Some(Position::new(4, 2, 0))
+            );
+
+            let mut pos = Position::new(5, 1, 0);
+            for _ in 0..map.len() {
+                match downhill(&map, pos, |_| true) {
+                    Some(next) => pos = next,
+                    None => break,
+                }
+            }
+            assert_eq!(pos, Position::new(1, 1, 0));
+        }
+
+        #[test]
+        fn downhill_stops_at_minimum() {
+            let map = map(20);
+            assert_eq!(downhill(&map, Position::new(1, 1, 0), |_| true), None);
+        }
+
+        #[test]
+        fn downhill_skips_occupied_tiles() {
+            let map = map(20);
+            let occupied = Position::new(4, 2, 0);
+            assert_eq!(
+                downhill(&map, Position::new(4, 1, 0), |pos| *pos != occupied),
+                None
+            );
+        }
+
+        #[test]
+        fn flee_map_leads_away_from_source() {
+            let floor = parse(MAP);
+            let source = Position::new(1, 1, 0);
+            let approach = map(20);
+            let flee = flee_map([source], 20, |pos| !floor.contains(pos));
+
+            let start = Position::new(2, 2, 0);
+            let mut pos = start;
+            for _ in 0..flee.len() {
+                match downhill(&flee, pos, |_| true) {
+                    Some(next) => pos = next,
+                    None => break,
+                }
+            }
+            assert!(
+                approach[&pos] > approach[&start],
+                "fled from {start:?} to {pos:?}"
+            );
+        }
+    }
 }
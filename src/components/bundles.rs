@@ -1,4 +1,7 @@
-use super::combat::{Defense, Health, Power, SufferDamage};
+use super::{
+    combat::{Defense, Health, Power, SufferDamage},
+    status::StatusEffects,
+};
 use bevy::prelude::Bundle;
 
 #[derive(Debug, PartialEq, Eq, Clone, Bundle)]
@@ -7,6 +10,7 @@ pub struct CombatStats {
     power: Power,
     defense: Defense,
     damage: SufferDamage,
+    status_effects: StatusEffects,
 }
 
 impl CombatStats {
@@ -16,6 +20,7 @@ impl CombatStats {
             power: Power(power),
             defense: Defense(defense),
             damage: SufferDamage::new(),
+            status_effects: StatusEffects::new(),
         }
     }
 }
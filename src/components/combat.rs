@@ -1,4 +1,5 @@
-use bevy::prelude::Component;
+use super::status::StatusEffect;
+use bevy::prelude::{Component, Entity};
 
 #[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
 pub struct Health {
@@ -20,29 +21,101 @@ impl Health {
         self.current -= damage;
     }
 
+    /// Restores health by `amount`, never going over `max`
+    pub fn heal(&mut self, amount: i32) {
+        self.current = i32::min(self.max, self.current + amount);
+    }
+
     pub fn is_dead(&self) -> bool {
         self.current <= self.min
     }
 }
 
+/// Entity slowly recovers health while there is no danger around
+#[derive(Debug, Default, PartialEq, Eq, Component, Clone, Copy)]
+pub struct NaturalRegeneration {
+    /// Turns spent out of combat since the last recovered point of health
+    quiet_turns: i32,
+}
+
+impl NaturalRegeneration {
+    pub fn new() -> Self {
+        Self { quiet_turns: 0 }
+    }
+
+    /// Number of quiet turns needed to recover one point of health, the higher the `level` the faster it gets
+    pub fn turns_per_point(level: i32) -> i32 {
+        i32::max(1, 10 - level)
+    }
+
+    /// Counts one more quiet turn. Returns `true` when enough of them passed to recover a point of health.
+    pub fn tick(&mut self, level: i32) -> bool {
+        self.quiet_turns += 1;
+        if self.quiet_turns >= Self::turns_per_point(level) {
+            self.quiet_turns = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Danger is near, start counting quiet turns from the beginning
+    pub fn interrupt(&mut self) {
+        self.quiet_turns = 0;
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
 pub struct Power(pub i32);
 
 #[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
 pub struct Defense(pub i32);
 
+/// Entity is able to attack from distance, up to `range` tiles away
+#[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
+pub struct RangedAttack {
+    pub range: i32,
+}
+
+impl RangedAttack {
+    pub fn new(range: i32) -> Self {
+        Self { range }
+    }
+}
+
+/// Each meelee hit that deals damage has a `chance` to apply the `effect` to the one being hit
+#[derive(Debug, PartialEq, Component, Clone, Copy)]
+pub struct InflictsOnHit {
+    pub effect: StatusEffect,
+    pub chance: f64,
+}
+
+impl InflictsOnHit {
+    pub fn new(effect: StatusEffect, chance: f64) -> Self {
+        Self { effect, chance }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Component)]
 pub struct SufferDamage {
     pub amount: Vec<i32>,
+    /// Entity that dealt the most recent damage, used to figure out who made the kill
+    pub last_source: Option<Entity>,
 }
 
 impl SufferDamage {
     pub fn new() -> Self {
-        Self { amount: vec![] }
+        Self {
+            amount: vec![],
+            last_source: None,
+        }
     }
 
-    pub fn add_damage(&mut self, damage: i32) {
-        self.amount.push(damage)
+    /// Adds damage to be applied. `source` is the entity responsible for the damage, if there is any.
+    pub fn add_damage(&mut self, damage: i32, source: Option<Entity>) {
+        self.amount.push(damage);
+        if source.is_some() {
+            self.last_source = source;
+        }
     }
 
     pub fn drain(&mut self) -> std::vec::Drain<'_, i32> {
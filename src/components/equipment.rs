@@ -0,0 +1,179 @@
+//! Items that can be worn and the bonuses they grant to their wearer
+use bevy::prelude::{Component, Entity};
+use std::fmt::Display;
+
+/// Place on the body where equipment is worn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EquipmentSlot {
+    MeleeWeapon,
+    Shield,
+    Armor,
+    Helmet,
+    Ring,
+}
+
+impl EquipmentSlot {
+    pub const ALL: [EquipmentSlot; 5] = [
+        EquipmentSlot::MeleeWeapon,
+        EquipmentSlot::Shield,
+        EquipmentSlot::Armor,
+        EquipmentSlot::Helmet,
+        EquipmentSlot::Ring,
+    ];
+
+    /// How many items can be worn in the slot at once
+    pub fn capacity(&self) -> usize {
+        match self {
+            EquipmentSlot::Ring => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for EquipmentSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquipmentSlot::MeleeWeapon => write!(f, "Weapon"),
+            EquipmentSlot::Shield => write!(f, "Shield"),
+            EquipmentSlot::Armor => write!(f, "Armor"),
+            EquipmentSlot::Helmet => write!(f, "Helmet"),
+            EquipmentSlot::Ring => write!(f, "Ring"),
+        }
+    }
+}
+
+/// Item can be worn in the `slot`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Equippable {
+    pub slot: EquipmentSlot,
+}
+
+impl Equippable {
+    pub fn new(slot: EquipmentSlot) -> Self {
+        Self { slot }
+    }
+}
+
+/// Item is currently worn by the `owner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Equipped {
+    pub owner: Entity,
+    pub slot: EquipmentSlot,
+}
+
+impl Equipped {
+    pub fn new(owner: Entity, slot: EquipmentSlot) -> Self {
+        Self { owner, slot }
+    }
+}
+
+/// Cursed equipment cannot be taken off once worn. The curse stays hidden until the item is put on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Cursed {
+    pub revealed: bool,
+}
+
+impl Cursed {
+    /// Appended to the name of the item once the curse is revealed
+    pub const NAME_SUFFIX: &'static str = " (cursed)";
+
+    pub fn new() -> Self {
+        Self { revealed: false }
+    }
+}
+
+impl Default for Cursed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Power added to the wearer's [super::combat::Power]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct PowerBonus(pub i32);
+
+/// Defense added to the wearer's [super::combat::Defense]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct DefenseBonus(pub i32);
+
+/// Kinds of equipment which can be spawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquipmentKind {
+    Dagger,
+    Longsword,
+    Shield,
+    LeatherArmor,
+    Helmet,
+    RingOfStrength,
+    RingOfProtection,
+}
+
+impl EquipmentKind {
+    pub fn slot(&self) -> EquipmentSlot {
+        match self {
+            EquipmentKind::Dagger | EquipmentKind::Longsword => EquipmentSlot::MeleeWeapon,
+            EquipmentKind::Shield => EquipmentSlot::Shield,
+            EquipmentKind::LeatherArmor => EquipmentSlot::Armor,
+            EquipmentKind::Helmet => EquipmentSlot::Helmet,
+            EquipmentKind::RingOfStrength | EquipmentKind::RingOfProtection => EquipmentSlot::Ring,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EquipmentKind::Dagger => "Dagger",
+            EquipmentKind::Longsword => "Longsword",
+            EquipmentKind::Shield => "Shield",
+            EquipmentKind::LeatherArmor => "Leather armor",
+            EquipmentKind::Helmet => "Helmet",
+            EquipmentKind::RingOfStrength => "Ring of strength",
+            EquipmentKind::RingOfProtection => "Ring of protection",
+        }
+    }
+
+    /// Returns `(power, defense)` bonuses granted by the equipment. Cursed equipment takes away what it
+    /// would otherwise grant.
+    pub fn bonuses(&self, cursed: bool) -> (i32, i32) {
+        let (power, defense) = self.base_bonuses();
+        match cursed {
+            true => (-power, -defense),
+            false => (power, defense),
+        }
+    }
+
+    fn base_bonuses(&self) -> (i32, i32) {
+        match self {
+            EquipmentKind::Dagger => (1, 0),
+            EquipmentKind::Longsword => (3, 0),
+            EquipmentKind::Shield => (0, 1),
+            EquipmentKind::LeatherArmor => (0, 2),
+            EquipmentKind::Helmet => (0, 1),
+            EquipmentKind::RingOfStrength => (1, 0),
+            EquipmentKind::RingOfProtection => (0, 1),
+        }
+    }
+}
+
+/// Sums up `(power, defense)` bonuses of all the `equipment` worn by the `owner`
+pub fn total_bonuses<'a>(
+    owner: Entity,
+    equipment: impl Iterator<
+        Item = (
+            &'a Equipped,
+            Option<&'a PowerBonus>,
+            Option<&'a DefenseBonus>,
+        ),
+    >,
+) -> (i32, i32) {
+    equipment
+        .filter(|(equipped, ..)| equipped.owner == owner)
+        .fold(
+            (0, 0),
+            |(power, defense), (_, power_bonus, defense_bonus)| {
+                (
+                    power + power_bonus.map(|bonus| bonus.0).unwrap_or_default(),
+                    defense + defense_bonus.map(|bonus| bonus.0).unwrap_or_default(),
+                )
+            },
+        )
+}
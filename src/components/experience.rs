@@ -0,0 +1,46 @@
+use bevy::prelude::Component;
+
+/// How much experience is granted to whoever kills the entity
+#[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
+pub struct XpValue(pub i32);
+
+/// Tracks entity's level and experience gained towards the next one
+#[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
+pub struct Experience {
+    pub level: i32,
+    pub current: i32,
+}
+
+impl Experience {
+    pub fn new() -> Self {
+        Self {
+            level: 1,
+            current: 0,
+        }
+    }
+
+    /// Experience needed to advance from the current level to the next one
+    pub fn next_level_xp(&self) -> i32 {
+        self.level * 100
+    }
+
+    pub fn gain(&mut self, xp: i32) {
+        self.current += xp;
+    }
+
+    pub fn can_level_up(&self) -> bool {
+        self.current >= self.next_level_xp()
+    }
+
+    /// Advances to the next level, experience over the threshold carries over
+    pub fn level_up(&mut self) {
+        self.current -= self.next_level_xp();
+        self.level += 1;
+    }
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,40 @@
+//! Factions decide who fights whom. Monsters pick their targets among the entities their faction is hostile to.
+use bevy::prelude::Component;
+
+/// How a member of one faction treats a member of another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    Hostile,
+    Neutral,
+    Friendly,
+}
+
+/// Side the entity is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub enum Faction {
+    Player,
+    Orcs,
+    Goblins,
+    Slimes,
+    /// Animals mind their own business and ignore everyone
+    Animals,
+}
+
+impl Faction {
+    /// Reaction table, tells how members of this faction treat members of the `other` one
+    pub fn reaction(&self, other: Faction) -> Reaction {
+        use Faction::*;
+
+        match (self, other) {
+            (a, b) if *a == b => Reaction::Friendly,
+            (Animals, _) | (_, Animals) => Reaction::Neutral,
+            (Player, _) | (_, Player) => Reaction::Hostile,
+            (Orcs, Goblins) | (Goblins, Orcs) => Reaction::Hostile,
+            _ => Reaction::Neutral,
+        }
+    }
+
+    pub fn is_hostile_to(&self, other: Faction) -> bool {
+        self.reaction(other) == Reaction::Hostile
+    }
+}
@@ -0,0 +1,67 @@
+//! Hunger clock. Entity gets hungrier every turn and has to eat to avoid starving.
+use bevy::prelude::Component;
+use std::fmt::Display;
+
+/// How hungry an entity is, derived from its [Hunger]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HungerState {
+    WellFed,
+    Normal,
+    Hungry,
+    Starving,
+}
+
+impl Display for HungerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HungerState::WellFed => write!(f, "Well Fed"),
+            HungerState::Normal => write!(f, "Normal"),
+            HungerState::Hungry => write!(f, "Hungry"),
+            HungerState::Starving => write!(f, "Starving"),
+        }
+    }
+}
+
+/// Entity needs to eat. `food` goes down by one each turn, once it reaches zero the entity is starving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Hunger {
+    pub food: i32,
+}
+
+impl Hunger {
+    /// Most food an entity can have stored
+    pub const MAX_FOOD: i32 = 1000;
+    const WELL_FED_ABOVE: i32 = 750;
+    const HUNGRY_BELOW: i32 = 250;
+
+    pub fn new() -> Self {
+        Self {
+            food: Self::MAX_FOOD,
+        }
+    }
+
+    pub fn state(&self) -> HungerState {
+        match self.food {
+            food if food > Self::WELL_FED_ABOVE => HungerState::WellFed,
+            food if food >= Self::HUNGRY_BELOW => HungerState::Normal,
+            food if food > 0 => HungerState::Hungry,
+            _ => HungerState::Starving,
+        }
+    }
+
+    /// One turn has passed
+    pub fn tick(&mut self) {
+        self.food = i32::max(self.food - 1, 0);
+    }
+
+    /// Adds `nutrition` to the stored food, up to [Hunger::MAX_FOOD]
+    pub fn eat(&mut self, nutrition: i32) {
+        self.food = i32::min(self.food + nutrition, Self::MAX_FOOD);
+    }
+}
+
+impl Default for Hunger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,5 @@
-use bevy::prelude::Component;
+use super::{equipment::EquipmentKind, status::StatusEffect};
+use bevy::prelude::{Component, Entity};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
 pub struct Item;
@@ -13,3 +14,177 @@ impl Potion {
         Self { amount }
     }
 }
+
+/// Using the item applies the effect to the user, see [super::status::StatusEffects]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+pub struct GrantsEffect(pub StatusEffect);
+
+/// Eating food satisfies hunger, see [super::hunger::Hunger]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+pub struct Food {
+    pub nutrition: i32,
+}
+
+impl Food {
+    pub fn new(nutrition: i32) -> Self {
+        Self { nutrition }
+    }
+}
+
+/// Scroll casts its spell once read and is consumed afterwards
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+pub enum Scroll {
+    /// Deals `damage` to everything within `radius` of the target
+    Fireball {
+        damage: i32,
+        range: i32,
+        radius: i32,
+    },
+    /// Confuses monsters within `radius` of the target for `turns`
+    Confusion { turns: u32, range: i32, radius: i32 },
+    /// Reveals the whole map
+    MagicMapping,
+    /// Lifts curses from everything the reader carries
+    RemoveCurse,
+    /// Lets the reader sense monsters within `radius` for `turns`, see [super::status::StatusEffectKind::Telepathy]
+    Telepathy { turns: u32, radius: i32 },
+    /// Reveals all the items lying on the level
+    DetectItems,
+}
+
+impl Scroll {
+    /// How far from the reader can the spell be targeted. [None] if the spell does not need a target
+    pub fn range(&self) -> Option<i32> {
+        match self {
+            Scroll::Fireball { range, .. } | Scroll::Confusion { range, .. } => Some(*range),
+            Scroll::MagicMapping
+            | Scroll::RemoveCurse
+            | Scroll::Telepathy { .. }
+            | Scroll::DetectItems => None,
+        }
+    }
+
+    /// Radius of the area affected by the spell. [None] if the spell does not need a target
+    pub fn radius(&self) -> Option<i32> {
+        match self {
+            Scroll::Fireball { radius, .. } | Scroll::Confusion { radius, .. } => Some(*radius),
+            Scroll::MagicMapping
+            | Scroll::RemoveCurse
+            | Scroll::Telepathy { .. }
+            | Scroll::DetectItems => None,
+        }
+    }
+}
+
+/// Magic items whose effects player has to learn. Until the kind is identified, all items of that kind go by
+/// the same randomly chosen appearance, see [crate::resources::ItemIdentities].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component)]
+pub enum MagicItemKind {
+    HealthPotion,
+    RegenerationPotion,
+    FireballScroll,
+    ConfusionScroll,
+    MagicMappingScroll,
+    RemoveCurseScroll,
+    TelepathyScroll,
+    DetectItemsScroll,
+}
+
+impl MagicItemKind {
+    pub const ALL: [MagicItemKind; 8] = [
+        MagicItemKind::HealthPotion,
+        MagicItemKind::RegenerationPotion,
+        MagicItemKind::FireballScroll,
+        MagicItemKind::ConfusionScroll,
+        MagicItemKind::MagicMappingScroll,
+        MagicItemKind::RemoveCurseScroll,
+        MagicItemKind::TelepathyScroll,
+        MagicItemKind::DetectItemsScroll,
+    ];
+
+    pub fn real_name(&self) -> &'static str {
+        match self {
+            MagicItemKind::HealthPotion => "Health Potion",
+            MagicItemKind::RegenerationPotion => "Regeneration Potion",
+            MagicItemKind::FireballScroll => "Fireball scroll",
+            MagicItemKind::ConfusionScroll => "Confusion scroll",
+            MagicItemKind::MagicMappingScroll => "Magic mapping scroll",
+            MagicItemKind::RemoveCurseScroll => "Remove curse scroll",
+            MagicItemKind::TelepathyScroll => "Telepathy scroll",
+            MagicItemKind::DetectItemsScroll => "Detect items scroll",
+        }
+    }
+
+    pub fn is_potion(&self) -> bool {
+        matches!(
+            self,
+            MagicItemKind::HealthPotion | MagicItemKind::RegenerationPotion
+        )
+    }
+}
+
+impl From<Scroll> for MagicItemKind {
+    fn from(scroll: Scroll) -> Self {
+        match scroll {
+            Scroll::Fireball { .. } => MagicItemKind::FireballScroll,
+            Scroll::Confusion { .. } => MagicItemKind::ConfusionScroll,
+            Scroll::MagicMapping => MagicItemKind::MagicMappingScroll,
+            Scroll::RemoveCurse => MagicItemKind::RemoveCurseScroll,
+            Scroll::Telepathy { .. } => MagicItemKind::TelepathyScroll,
+            Scroll::DetectItems => MagicItemKind::DetectItemsScroll,
+        }
+    }
+}
+
+/// Kinds of items which can be spawned
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ItemKind {
+    HealthPotion,
+    RegenerationPotion,
+    Ration,
+    LampOil,
+    Scroll(Scroll),
+    /// Cursed equipment has its bonuses reversed and cannot be taken off, see [super::equipment::Cursed]
+    Equipment {
+        kind: EquipmentKind,
+        cursed: bool,
+    },
+}
+
+impl ItemKind {
+    /// How much gold the item costs at a vendor
+    pub fn price(&self) -> i32 {
+        match self {
+            ItemKind::HealthPotion => 20,
+            ItemKind::RegenerationPotion => 30,
+            ItemKind::Ration => 10,
+            ItemKind::LampOil => 15,
+            ItemKind::Scroll(Scroll::Fireball { .. }) => 40,
+            ItemKind::Scroll(Scroll::Confusion { .. }) => 30,
+            ItemKind::Scroll(Scroll::MagicMapping) => 50,
+            ItemKind::Scroll(Scroll::RemoveCurse) => 40,
+            ItemKind::Scroll(Scroll::Telepathy { .. }) => 45,
+            ItemKind::Scroll(Scroll::DetectItems) => 35,
+            ItemKind::Equipment { kind, .. } => match kind {
+                EquipmentKind::Dagger => 15,
+                EquipmentKind::Longsword => 60,
+                EquipmentKind::Shield => 25,
+                EquipmentKind::LeatherArmor => 35,
+                EquipmentKind::Helmet => 20,
+                EquipmentKind::RingOfStrength | EquipmentKind::RingOfProtection => 50,
+            },
+        }
+    }
+}
+
+/// Item is carried in the backpack of the `owner`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+pub struct InBackpack {
+    pub owner: Entity,
+}
+
+impl InBackpack {
+    pub fn new(owner: Entity) -> Self {
+        Self { owner }
+    }
+}
@@ -0,0 +1,98 @@
+//! Light sources. Each source lights up the tiles it can see within its radius, tiles no light reaches are dark.
+use bevy::prelude::{Color, Component};
+
+/// Entity emitting light of the given `color`. `intensity` is the brightness right at the source, it fades
+/// out towards the edge of the `radius`.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct LightSource {
+    pub radius: i32,
+    pub color: Color,
+    pub intensity: f32,
+}
+
+impl LightSource {
+    pub fn new(radius: i32, color: Color, intensity: f32) -> Self {
+        Self {
+            radius,
+            color,
+            intensity,
+        }
+    }
+
+    /// How bright the light is `distance` tiles away from the source
+    pub fn intensity_at(&self, distance: i32) -> f32 {
+        self.intensity * (1. - distance as f32 / (self.radius + 1) as f32)
+    }
+}
+
+/// Lantern burning one unit of `fuel` each turn. It gets dimmer once the fuel runs low and goes out once the fuel
+/// is gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Lantern {
+    pub fuel: i32,
+}
+
+impl Lantern {
+    /// Most fuel the lantern can hold
+    pub const MAX_FUEL: i32 = 1000;
+    /// Below this much fuel the lantern gets dimmer
+    const LOW_FUEL: i32 = 100;
+    /// How far a lantern with enough fuel shines
+    const RADIUS: i32 = 5;
+    const COLOR: Color = Color::rgb(1., 0.85, 0.6);
+
+    pub fn new() -> Self {
+        Self {
+            fuel: Self::MAX_FUEL,
+        }
+    }
+
+    /// One turn has passed
+    pub fn tick(&mut self) {
+        self.fuel = i32::max(self.fuel - 1, 0);
+    }
+
+    /// Adds `fuel`, up to [Lantern::MAX_FUEL]
+    pub fn refill(&mut self, fuel: i32) {
+        self.fuel = i32::min(self.fuel + fuel, Self::MAX_FUEL);
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.fuel < Self::LOW_FUEL
+    }
+
+    /// Light the lantern emits, [None] once it ran out of fuel
+    pub fn light(&self) -> Option<LightSource> {
+        match self.fuel {
+            0 => None,
+            fuel if fuel < Self::LOW_FUEL => Some(LightSource::new(
+                1 + (Self::RADIUS - 1) * fuel / Self::LOW_FUEL,
+                Self::COLOR,
+                0.5 + 0.5 * fuel as f32 / Self::LOW_FUEL as f32,
+            )),
+            _ => Some(LightSource::new(Self::RADIUS, Self::COLOR, 1.)),
+        }
+    }
+}
+
+impl Default for Lantern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Oil which refills a [Lantern] with `fuel`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+pub struct LampOil {
+    pub fuel: i32,
+}
+
+impl LampOil {
+    pub fn new(fuel: i32) -> Self {
+        Self { fuel }
+    }
+}
+
+/// Colour of the sprite before it is tinted by the light falling on it
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct BaseColor(pub Color);
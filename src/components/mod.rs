@@ -1,10 +1,19 @@
 pub mod bundles;
 pub mod combat;
+pub mod equipment;
+pub mod experience;
+pub mod faction;
+pub mod hunger;
 pub mod item;
+pub mod light;
+pub mod monster;
 pub mod requests;
+pub mod shop;
+pub mod spell;
+pub mod status;
 pub mod ui;
 
-use crate::consts::SPRITE_SIZE;
+use crate::{algorithms::fov::FovMode, consts::SPRITE_SIZE};
 use bevy::{
     prelude::{Component, Vec3},
     utils::hashbrown::HashSet,
@@ -162,6 +171,14 @@ impl Hash for Position {
 #[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
 pub struct Player;
 
+/// How good the entity is at sneaking around, the higher the less likely it is to wake sleeping monsters
+#[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
+pub struct Stealth(pub i32);
+
+/// Player keeps waiting turn after turn until healed or interrupted
+#[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
+pub struct Resting;
+
 /// Marks entity as a wall, it blocks player from going through as well as blocks sight
 #[derive(Debug, PartialEq, Eq, Component, Clone, Copy)]
 pub struct Wall;
@@ -181,6 +198,8 @@ pub struct Viewshed {
     pub visible_range: u8,
     /// which positions are currently in player's field of view
     pub visible_tiles: HashSet<Position>,
+    /// algorithm used to compute the field of view
+    pub mode: FovMode,
 }
 
 impl Viewshed {
@@ -188,9 +207,15 @@ impl Viewshed {
         Self {
             visible_range,
             visible_tiles: HashSet::new(),
+            mode: FovMode::default(),
         }
     }
 
+    pub fn with_mode(mut self, mode: FovMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn visible_range(&self) -> u8 {
         self.visible_range
     }
@@ -0,0 +1,119 @@
+//! Components describing monsters, what kind they are and what happens when they die
+use super::{item::ItemKind, Position};
+use bevy::prelude::Component;
+
+/// Kind of the monster, used when a monster of the same kind has to be spawned again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub enum MonsterKind {
+    Orc,
+    Goblin,
+    GoblinArcher,
+    GoblinShaman,
+    Slime,
+    SmallSlime,
+    Rat,
+}
+
+/// Effect that is triggered when the entity dies
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeathEffect {
+    /// Deals `damage` to everything within `radius`
+    Explode { radius: i32, damage: i32 },
+    /// Spawns up to `count` monsters of kind `into` next to the dead one
+    Split { into: MonsterKind, count: u8 },
+    /// Rolls the loot table and drops everything that was rolled
+    DropLoot(LootTable),
+}
+
+/// Effects triggered on entity's death
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct OnDeath(pub Vec<DeathEffect>);
+
+/// List of items which can drop, each with its own chance to drop from `0.0` to `1.0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTable(pub Vec<(ItemKind, f32)>);
+
+impl LootTable {
+    /// Returns all the items that should drop. Each item is rolled separately.
+    pub fn roll(&self) -> Vec<ItemKind> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        self.0
+            .iter()
+            .filter(|(_, chance)| rng.gen_range(0f32..1f32) < *chance)
+            .map(|(kind, _)| *kind)
+            .collect()
+    }
+}
+
+/// Remains of a dead monster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Corpse;
+
+/// Monster is asleep and does nothing until something wakes it up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Sleeping;
+
+/// Position a wandering monster is heading to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct WanderGoal(pub Position);
+
+/// Where the monster last saw the player. The memory fades after `turns_left` turns without seeing the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct PlayerMemory {
+    pub last_seen: Position,
+    pub turns_left: u32,
+}
+
+impl PlayerMemory {
+    /// For how many turns monster remembers where it saw the player
+    pub const DURATION: u32 = 8;
+
+    pub fn new(last_seen: Position) -> Self {
+        Self {
+            last_seen,
+            turns_left: Self::DURATION,
+        }
+    }
+}
+
+/// Hostile entity the monster has chosen to fight, see [crate::ai::select_targets]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Target(pub bevy::prelude::Entity);
+
+/// Monster hunts together with its allies. Pack members surround their target instead of queueing up behind each
+/// other and alert allies nearby when they spot the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Pack;
+
+/// Situations in which a monster may shout something at the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarkTrigger {
+    /// Monster has just spotted the player
+    Spotted,
+    /// Monster got hurt
+    Wounded,
+    /// Monster is running away
+    Fleeing,
+}
+
+impl BarkTrigger {
+    /// Chance the monster actually says something, so the log is not flooded with barks
+    pub fn chance(&self) -> f64 {
+        match self {
+            BarkTrigger::Spotted => 0.5,
+            BarkTrigger::Wounded => 0.25,
+            BarkTrigger::Fleeing => 0.2,
+        }
+    }
+}
+
+/// Where the monster heard a noise it is going to investigate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct HeardSound(pub Position);
+
+/// Steps the monster plans to take, starting with the next one. Only kept for the turn it was planned in, so it can be
+/// shown by the AI debug overlay.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct PlannedPath(pub Vec<Position>);
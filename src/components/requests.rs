@@ -2,7 +2,9 @@
 //!
 //!
 //!
+use super::{spell::Spell, Position};
 use bevy::prelude::{Component, Entity};
+use rand::Rng;
 
 /// Component to request movement. The values indicate bych how much the entity should move by.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Component)]
@@ -24,6 +26,17 @@ impl MovementRequest {
     pub fn left() -> Self {
         MovementRequest { x: -1, y: 0 }
     }
+
+    /// Movement by one tile in a random direction, diagonals included
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        loop {
+            let (x, y) = (rng.gen_range(-1..=1), rng.gen_range(-1..=1));
+            if x != 0 || y != 0 {
+                return MovementRequest { x, y };
+            }
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Component)]
@@ -37,3 +50,50 @@ impl MeeleeAttackRequest {
         Self { target }
     }
 }
+
+/// Component to request an attack from distance. The attack fails if the `target` is further than `range` or there is
+/// no clear line of fire.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Component)]
+pub struct RangedAttackRequest {
+    pub target: Entity,
+    pub range: i32,
+}
+
+impl RangedAttackRequest {
+    pub fn new(target: Entity, range: i32) -> Self {
+        Self { target, range }
+    }
+}
+
+/// Component to request using an item from the backpack. Items that affect an area are aimed at `target`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Component)]
+pub struct UseItemRequest {
+    pub item: Entity,
+    pub target: Option<Position>,
+}
+
+impl UseItemRequest {
+    pub fn new(item: Entity, target: Option<Position>) -> Self {
+        Self { item, target }
+    }
+}
+
+/// What the spell is cast at, summoning spells are cast at an empty tile
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SpellTarget {
+    Entity(Entity),
+    Position(Position),
+}
+
+/// Component to request casting a `spell` at the `target`
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Component)]
+pub struct CastSpellRequest {
+    pub spell: Spell,
+    pub target: SpellTarget,
+}
+
+impl CastSpellRequest {
+    pub fn new(spell: Spell, target: SpellTarget) -> Self {
+        Self { spell, target }
+    }
+}
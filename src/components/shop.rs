@@ -0,0 +1,47 @@
+//! Gold and trading with vendors
+use bevy::prelude::Component;
+
+/// Gold carried by an entity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component)]
+pub struct Wallet {
+    pub gold: i32,
+}
+
+impl Wallet {
+    pub fn new(gold: i32) -> Self {
+        Self { gold }
+    }
+
+    pub fn earn(&mut self, amount: i32) {
+        self.gold += amount;
+    }
+
+    /// Takes `amount` of gold out of the wallet. Returns `false` and leaves the wallet untouched if there is not
+    /// enough gold in it.
+    pub fn pay(&mut self, amount: i32) -> bool {
+        if self.gold < amount {
+            return false;
+        }
+        self.gold -= amount;
+        true
+    }
+}
+
+/// Pile of gold lying on the floor, collected by walking over it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct GoldPile(pub i32);
+
+/// Peaceful NPC that buys and sells items. Items it has in stock are carried in its backpack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Vendor;
+
+/// How much gold the item costs when bought from a vendor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Price(pub i32);
+
+impl Price {
+    /// Vendors buy items for half of their price
+    pub fn sell_value(&self) -> i32 {
+        i32::max(1, self.0 / 2)
+    }
+}
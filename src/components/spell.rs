@@ -0,0 +1,93 @@
+//! Spells monsters can cast. Each spell the caster knows has its own cooldown.
+use super::monster::MonsterKind;
+use bevy::prelude::Component;
+use std::fmt::Display;
+
+/// Kind of the spell, ignoring its strength. Used by the AI to decide which spell to cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpellKind {
+    Heal,
+    Bolt,
+    Summon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spell {
+    /// Restores `amount` of health to a wounded ally
+    Heal { amount: i32 },
+    /// Deals `damage` to a target within `range` and with a clear line of fire
+    Bolt { damage: i32, range: i32 },
+    /// Calls a monster of given `kind` to fight alongside the caster
+    Summon { kind: MonsterKind },
+}
+
+impl Spell {
+    pub fn kind(&self) -> SpellKind {
+        match self {
+            Spell::Heal { .. } => SpellKind::Heal,
+            Spell::Bolt { .. } => SpellKind::Bolt,
+            Spell::Summon { .. } => SpellKind::Summon,
+        }
+    }
+}
+
+impl Display for Spell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Spell::Heal { .. } => write!(f, "Heal"),
+            Spell::Bolt { .. } => write!(f, "Bolt"),
+            Spell::Summon { .. } => write!(f, "Summon"),
+        }
+    }
+}
+
+/// Spell known by the caster together with how many turns have to pass before it can be cast again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownSpell {
+    pub spell: Spell,
+    pub cooldown: u32,
+    pub ready_in: u32,
+}
+
+/// Spells the entity can cast
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct Spellbook(pub Vec<KnownSpell>);
+
+impl Spellbook {
+    /// Spellbook with all the `spells` ready to be cast, each paired with its cooldown
+    pub fn new(spells: impl IntoIterator<Item = (Spell, u32)>) -> Self {
+        Self(
+            spells
+                .into_iter()
+                .map(|(spell, cooldown)| KnownSpell {
+                    spell,
+                    cooldown,
+                    ready_in: 0,
+                })
+                .collect(),
+        )
+    }
+
+    /// Spell of given `kind` if it is known and ready to be cast
+    pub fn ready(&self, kind: SpellKind) -> Option<Spell> {
+        self.0
+            .iter()
+            .find(|known| known.spell.kind() == kind && known.ready_in == 0)
+            .map(|known| known.spell)
+    }
+
+    /// Spell of given `kind` has just been cast, so it has to cool down
+    pub fn start_cooldown(&mut self, kind: SpellKind) {
+        self.0
+            .iter_mut()
+            .filter(|known| known.spell.kind() == kind)
+            .for_each(|known| known.ready_in = known.cooldown);
+    }
+
+    /// One more turn has passed
+    pub fn tick(&mut self) {
+        self.0
+            .iter_mut()
+            .for_each(|known| known.ready_in = known.ready_in.saturating_sub(1));
+    }
+}
@@ -0,0 +1,105 @@
+//! Status effects are temporary effects that entities carry around for a limited number of turns.
+//! Each turn boundary the remaining turns are decreased and expired effects are removed.
+use bevy::prelude::Component;
+use std::fmt::Display;
+
+/// Kinds of effects that can be applied to an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusEffectKind {
+    /// Deals `damage` each turn
+    Poison { damage: i32 },
+    /// Movement direction is randomized
+    Confusion,
+    /// Entity skips its turns
+    Stun,
+    /// Heals `amount` each turn
+    Regeneration { amount: i32 },
+    /// Senses all monsters within `radius`, even those out of sight
+    Telepathy { radius: i32 },
+}
+
+impl StatusEffectKind {
+    /// Whether both kinds are the same effect, ignoring their strength
+    fn same_kind(&self, other: &StatusEffectKind) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl Display for StatusEffectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusEffectKind::Poison { .. } => write!(f, "Poisoned"),
+            StatusEffectKind::Confusion => write!(f, "Confused"),
+            StatusEffectKind::Stun => write!(f, "Stunned"),
+            StatusEffectKind::Regeneration { .. } => write!(f, "Regenerating"),
+            StatusEffectKind::Telepathy { .. } => write!(f, "Telepathic"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    /// For how many more turns the effect will be active
+    pub turns_left: u32,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusEffectKind, turns_left: u32) -> Self {
+        Self { kind, turns_left }
+    }
+}
+
+/// All the effects that are currently active on the entity
+#[derive(Debug, Clone, Default, PartialEq, Eq, Component)]
+pub struct StatusEffects(Vec<StatusEffect>);
+
+impl StatusEffects {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    /// Adds new effect. If the same kind of effect is already active, it is replaced by the new one
+    /// while keeping the longer of the two durations.
+    pub fn add(&mut self, effect: StatusEffect) {
+        match self.0.iter_mut().find(|e| e.kind.same_kind(&effect.kind)) {
+            Some(active) => {
+                active.turns_left = u32::max(active.turns_left, effect.turns_left);
+                active.kind = effect.kind;
+            }
+            None => self.0.push(effect),
+        }
+    }
+
+    pub fn is_confused(&self) -> bool {
+        self.0
+            .iter()
+            .any(|e| matches!(e.kind, StatusEffectKind::Confusion))
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.0
+            .iter()
+            .any(|e| matches!(e.kind, StatusEffectKind::Stun))
+    }
+
+    /// How far the entity senses monsters, [None] if it is not telepathic
+    pub fn telepathy_radius(&self) -> Option<i32> {
+        self.0.iter().find_map(|e| match e.kind {
+            StatusEffectKind::Telepathy { radius } => Some(radius),
+            _ => None,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.0.iter()
+    }
+
+    /// Decreases remaining turns of all effects by one and removes those that expired
+    pub fn tick(&mut self) {
+        self.0
+            .iter_mut()
+            .for_each(|e| e.turns_left = e.turns_left.saturating_sub(1));
+        self.0.retain(|e| e.turns_left > 0);
+    }
+}
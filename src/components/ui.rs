@@ -12,6 +12,14 @@ pub struct HpNode;
 #[derive(Debug, Component, Copy, Clone)]
 pub struct HpText;
 
+/// Text showing how hungry the player is
+#[derive(Debug, Component, Copy, Clone)]
+pub struct HungerText;
+
+/// Text listing status effects currently active on the player
+#[derive(Debug, Component, Copy, Clone)]
+pub struct StatusEffectsText;
+
 /// Entity IDs of messagess that should be displayed by the combat log
 #[derive(Debug, Component, Copy, Clone)]
 pub struct Messages([Option<Entity>; 5]);
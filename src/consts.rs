@@ -7,8 +7,10 @@ pub const SPRITE_SIZE: f32 = 32f32;
 pub const PLAYER_Z: f32 = 20f32;
 pub const FLOOR_Z: f32 = 0f32;
 pub const WALL_Z: f32 = 1f32;
-pub const ITEM_Z: f32 = 2f32;
+pub const CORPSE_Z: f32 = 2f32;
+pub const ITEM_Z: f32 = 3f32;
 pub const MONSTER_Z: f32 = 10f32;
+pub const HIGHLIGHT_Z: f32 = 30f32;
 
 // MISC
 /// This is how much opacity the sprite should have when hidden by Fog of War
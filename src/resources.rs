@@ -1,5 +1,17 @@
-use crate::components::Position;
-use bevy::{math::Vec2, prelude::Resource};
+use crate::{
+    algorithms::dijkstra::dijkstra_map,
+    components::{
+        item::MagicItemKind,
+        monster::{BarkTrigger, MonsterKind},
+        Position,
+    },
+};
+use bevy::{
+    math::Vec2,
+    prelude::{Color, Resource},
+    utils::{HashMap, HashSet},
+};
+use rand::seq::SliceRandom;
 
 #[derive(Debug, Copy, Clone, Resource)]
 pub struct CursorPosition {
@@ -32,3 +44,275 @@ impl Default for CursorPosition {
         }
     }
 }
+
+/// What will happen once player confirms the selected target
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TargetingAction {
+    RangedAttack,
+    /// Use of an item from the backpack, aimed at the selected tile
+    UseItem(bevy::prelude::Entity),
+}
+
+/// Present while player is choosing a target, see [crate::states::GameState::Targeting]
+#[derive(Debug, Copy, Clone, Resource)]
+pub struct Targeting {
+    pub action: TargetingAction,
+    /// How far from the player the target can be
+    pub range: i32,
+    /// Radius of the area around the target that will be affected. [None] if only the target tile is affected
+    pub radius: Option<i32>,
+    /// Currently selected tile
+    pub target: Option<Position>,
+}
+
+impl Targeting {
+    pub fn new(action: TargetingAction, range: i32) -> Self {
+        Self {
+            action,
+            range,
+            radius: None,
+            target: None,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: i32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+}
+
+/// Looks potions can have before they are identified
+const POTION_APPEARANCES: [&str; 8] = [
+    "murky", "bubbling", "cloudy", "fizzy", "glowing", "smoky", "viscous", "swirling",
+];
+
+/// Syllables that make up labels of unidentified scrolls
+const SCROLL_SYLLABLES: [&str; 12] = [
+    "xyz", "zy", "ab", "zor", "ka", "lum", "mir", "tor", "vex", "ul", "ne", "qua",
+];
+
+/// Per-run mapping of [MagicItemKind]s to the appearances they go by until player identifies them
+#[derive(Debug, Clone, Resource)]
+pub struct ItemIdentities {
+    appearances: HashMap<MagicItemKind, String>,
+    identified: HashSet<MagicItemKind>,
+}
+
+impl ItemIdentities {
+    /// Randomly assigns a distinct appearance to each kind of magic item
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut potions = POTION_APPEARANCES.to_vec();
+        potions.shuffle(&mut rng);
+        let mut potions = potions.into_iter();
+        let mut labels = HashSet::new();
+
+        let appearances = MagicItemKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let appearance = match kind.is_potion() {
+                    true => format!("{} potion", potions.next().unwrap_or("strange")),
+                    false => loop {
+                        let label = SCROLL_SYLLABLES
+                            .choose_multiple(&mut rng, 2)
+                            .copied()
+                            .collect::<String>()
+                            .to_uppercase();
+                        if labels.insert(label.clone()) {
+                            break format!("scroll labelled {label}");
+                        }
+                    },
+                };
+                (kind, appearance)
+            })
+            .collect();
+
+        Self {
+            appearances,
+            identified: HashSet::new(),
+        }
+    }
+
+    /// Name under which items of the `kind` are shown, the real one only once the kind is identified
+    pub fn name(&self, kind: MagicItemKind) -> String {
+        match self.identified.contains(&kind) {
+            true => kind.real_name().to_string(),
+            false => self
+                .appearances
+                .get(&kind)
+                .cloned()
+                .unwrap_or_else(|| kind.real_name().to_string()),
+        }
+    }
+
+    /// Marks the `kind` as identified. Returns `true` if it was not identified before.
+    pub fn identify(&mut self, kind: MagicItemKind) -> bool {
+        self.identified.insert(kind)
+    }
+}
+
+/// Whether player is buying from the vendor or selling to it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShopMode {
+    Buy,
+    Sell,
+}
+
+/// Present while player is trading with a `vendor`, see [crate::states::GameState::Shop]
+#[derive(Debug, Copy, Clone, Resource)]
+pub struct Shopping {
+    pub vendor: bevy::prelude::Entity,
+    pub mode: ShopMode,
+}
+
+impl Shopping {
+    pub fn new(vendor: bevy::prelude::Entity) -> Self {
+        Self {
+            vendor,
+            mode: ShopMode::Buy,
+        }
+    }
+}
+
+/// Dijkstra maps shared by all the monsters, recomputed once at the start of every enemy turn, so that monsters
+/// only have to look at their neighbouring tiles to decide where to go
+#[derive(Debug, Clone, Default, Resource)]
+pub struct DijkstraMaps {
+    /// Distance of each tile to the player
+    pub approach_player: HashMap<Position, i32>,
+    /// Leads away from the player, see [crate::algorithms::dijkstra::flee_map]
+    pub flee_player: HashMap<Position, i32>,
+    /// Distance of each tile to the goal position, for monsters heading somewhere other than the player. Built the
+    /// first time any monster asks for the goal during the turn, see [DijkstraMaps::goal].
+    pub goals: HashMap<Position, HashMap<Position, i32>>,
+    /// Tiles monsters can walk on this turn, goal maps are built over them
+    pub walkable: HashSet<Position>,
+}
+
+impl DijkstraMaps {
+    /// How far from its goal a goal map reaches
+    pub const GOAL_MAP_RANGE: i32 = 40;
+
+    /// Forgets the goal maps of the previous turn, the `walkable` tiles may have changed since
+    pub fn reset_goals(&mut self, walkable: HashSet<Position>) {
+        self.goals.clear();
+        self.walkable = walkable;
+    }
+
+    /// Map leading to the `goal`, built on the first request during the turn and shared afterwards
+    pub fn goal(&mut self, goal: Position) -> &HashMap<Position, i32> {
+        if !self.goals.contains_key(&goal) {
+            let map = dijkstra_map([goal], Self::GOAL_MAP_RANGE, |pos| {
+                !self.walkable.contains(pos)
+            });
+            self.goals.insert(goal, map);
+        }
+        &self.goals[&goal]
+    }
+}
+
+/// Light falling on each tile, summed up over all the light sources. Tiles missing from the map are dark.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct LightMap(HashMap<Position, Color>);
+
+impl LightMap {
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Adds `light` to the light already falling on the `position`
+    pub fn add(&mut self, position: Position, light: Color) {
+        let total = self.0.entry(position).or_insert(Color::BLACK);
+        *total = Color::rgb(
+            f32::min(total.r() + light.r(), 1.),
+            f32::min(total.g() + light.g(), 1.),
+            f32::min(total.b() + light.b(), 1.),
+        );
+    }
+
+    /// Light falling on the `position`, [None] if it is dark
+    pub fn light_at(&self, position: &Position) -> Option<Color> {
+        self.0.get(position).copied()
+    }
+
+    pub fn is_lit(&self, position: &Position) -> bool {
+        self.0.contains_key(position)
+    }
+}
+
+/// Lines monsters shout at the player, per kind of monster and situation. Kinds missing from the table cannot talk.
+const BARK_LINES: &[(&[MonsterKind], &[BarkTrigger], &[&str])] = &[
+    (
+        &[MonsterKind::Orc],
+        &[BarkTrigger::Spotted],
+        &["Waaagh!", "Fresh meat!", "You're mine, runt!"],
+    ),
+    (
+        &[MonsterKind::Orc],
+        &[BarkTrigger::Wounded],
+        &["Is that all you got?", "Grrr!"],
+    ),
+    (
+        &[MonsterKind::Orc],
+        &[BarkTrigger::Fleeing],
+        &["This ain't over!"],
+    ),
+    (
+        &[
+            MonsterKind::Goblin,
+            MonsterKind::GoblinArcher,
+            MonsterKind::GoblinShaman,
+        ],
+        &[BarkTrigger::Spotted],
+        &["Intruder!", "Get 'em, boys!", "Shiny things! Give!"],
+    ),
+    (
+        &[
+            MonsterKind::Goblin,
+            MonsterKind::GoblinArcher,
+            MonsterKind::GoblinShaman,
+        ],
+        &[BarkTrigger::Wounded],
+        &["Ow! Ow!", "No fair!"],
+    ),
+    (
+        &[
+            MonsterKind::Goblin,
+            MonsterKind::GoblinArcher,
+            MonsterKind::GoblinShaman,
+        ],
+        &[BarkTrigger::Fleeing],
+        &["Run away!", "Mommy!", "Not the face!"],
+    ),
+    (
+        &[MonsterKind::Slime, MonsterKind::SmallSlime],
+        &[BarkTrigger::Spotted, BarkTrigger::Wounded],
+        &["*blorp*"],
+    ),
+    (&[MonsterKind::Rat], &[BarkTrigger::Wounded], &["*squeak*"]),
+];
+
+/// Lines each [MonsterKind] picks from when barking on a [BarkTrigger], see [BARK_LINES]
+#[derive(Debug, Clone, Resource)]
+pub struct BarkLines(HashMap<(MonsterKind, BarkTrigger), &'static [&'static str]>);
+
+impl BarkLines {
+    /// Lines the `kind` of monster can bark on the `trigger`, empty if it has nothing to say
+    pub fn get(&self, kind: MonsterKind, trigger: BarkTrigger) -> &'static [&'static str] {
+        self.0.get(&(kind, trigger)).copied().unwrap_or_default()
+    }
+}
+
+impl Default for BarkLines {
+    fn default() -> Self {
+        let mut lines = HashMap::new();
+        for (kinds, triggers, kind_lines) in BARK_LINES {
+            for kind in kinds.iter() {
+                for trigger in triggers.iter() {
+                    lines.insert((*kind, *trigger), *kind_lines);
+                }
+            }
+        }
+        Self(lines)
+    }
+}
@@ -6,5 +6,13 @@ pub enum GameState {
     #[default]
     PlayerTurn,
     EnemyTurn,
+    /// Player has gathered enough experience and is choosing what to improve
+    LevelUp,
+    /// Player is browsing the backpack
+    Inventory,
+    /// Player is selecting a target, see [crate::resources::Targeting]
+    Targeting,
+    /// Player is trading with a vendor, see [crate::resources::Shopping]
+    Shop,
     PlayerDead,
 }
@@ -0,0 +1,96 @@
+//! Barks are flavour lines monsters shout at the player when they spot it, get hurt or run away.
+//! They show up in the log and for a short while in a speech bubble above the monster. The lines themselves live
+//! in [BarkLines].
+
+use crate::{
+    ai::CurseAtPlayer,
+    components::{monster::MonsterKind, Name, Visible},
+    consts::{FONT_SIZE, SPRITE_SIZE},
+    resources::BarkLines,
+    ui::log::LogMessage,
+};
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+/// For how many seconds the speech bubble stays above the monster
+const SPEECH_BUBBLE_SECONDS: f32 = 2.0;
+
+/// Text floating above a monster that has just barked
+#[derive(Debug, Clone, Component)]
+pub struct SpeechBubble(Timer);
+
+pub(super) struct BarkPlugin;
+
+impl Plugin for BarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CurseAtPlayer>()
+            .init_resource::<BarkLines>()
+            .add_systems(Update, (curse_at_player, fade_speech_bubbles));
+    }
+}
+
+/// Picks a line for each [CurseAtPlayer] the player can witness, logs it and shows it above the monster
+fn curse_at_player(
+    mut cmd: Commands,
+    mut barks: EventReader<CurseAtPlayer>,
+    mut log_event_writer: EventWriter<LogMessage>,
+    bark_lines: Res<BarkLines>,
+    monsters: Query<(&Name, &MonsterKind, Has<Visible>)>,
+    bubbles: Query<(Entity, &Parent), With<SpeechBubble>>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for CurseAtPlayer { monster, trigger } in barks.read() {
+        let Ok((name, kind, visible)) = monsters.get(*monster) else {
+            continue;
+        };
+        if !visible || !rng.gen_bool(trigger.chance()) {
+            continue;
+        }
+        let Some(line) = bark_lines.get(*kind, *trigger).choose(&mut rng) else {
+            continue;
+        };
+
+        trace!(%name, ?trigger, %line, "barking");
+        log_event_writer.send(LogMessage::Bark {
+            time: chrono::Local::now(),
+            name: name.clone(),
+            line: line.to_string(),
+        });
+
+        // only the latest bark is shown
+        bubbles
+            .iter()
+            .filter(|(_, parent)| parent.get() == *monster)
+            .for_each(|(bubble, _)| cmd.entity(bubble).despawn_recursive());
+        cmd.entity(*monster).with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        *line,
+                        TextStyle {
+                            font_size: FONT_SIZE,
+                            color: Color::ORANGE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(0., SPRITE_SIZE * 0.75, 1.),
+                    ..default()
+                },
+                SpeechBubble(Timer::from_seconds(SPEECH_BUBBLE_SECONDS, TimerMode::Once)),
+            ));
+        });
+    }
+}
+
+fn fade_speech_bubbles(
+    mut cmd: Commands,
+    time: Res<Time>,
+    mut bubbles: Query<(Entity, &mut SpeechBubble)>,
+) {
+    for (bubble, mut speech_bubble) in bubbles.iter_mut() {
+        if speech_bubble.0.tick(time.delta()).finished() {
+            cmd.entity(bubble).despawn_recursive();
+        }
+    }
+}
@@ -2,75 +2,123 @@
 //!
 //!
 
+use super::{map::SpawnRequest, monster::Noise, targeting::blast_area};
 use crate::{
+    ai::CurseAtPlayer,
+    algorithms::line::has_line_of_fire,
     components::{
-        combat::{Defense, Health, Power, SufferDamage},
-        requests::MeeleeAttackRequest,
-        Name, Player,
+        combat::{Defense, Health, InflictsOnHit, Power, SufferDamage},
+        equipment::{total_bonuses, DefenseBonus, Equipped, PowerBonus},
+        experience::{Experience, XpValue},
+        item::InBackpack,
+        monster::{BarkTrigger, Corpse, DeathEffect, OnDeath},
+        requests::{CastSpellRequest, MeeleeAttackRequest, RangedAttackRequest, SpellTarget},
+        spell::Spell,
+        status::StatusEffects,
+        BlocksSight, BlocksTile, Monster, Name, Player, Position,
     },
+    consts::{CORPSE_Z, ITEM_Z},
     ui::log::LogMessage,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+use rand::Rng;
+use std::f32::consts::FRAC_PI_2;
 
 pub struct CombatSystemPlugin;
 impl Plugin for CombatSystemPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (combat_system, (apply_damage, delete_the_dead).chain()),
+            (
+                make_combat_noise
+                    .before(combat_system)
+                    .before(ranged_combat_system)
+                    .before(spell_system),
+                combat_system,
+                ranged_combat_system,
+                spell_system,
+                (
+                    apply_damage,
+                    trigger_death_effects,
+                    leave_remains,
+                    delete_the_dead,
+                )
+                    .chain(),
+            ),
         );
     }
 }
 
+/// How far the sound of fighting carries
+const COMBAT_VOLUME: i32 = 10;
+
+/// Entities about to attack or cast a spell
+type Combatants<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Position),
+    Or<(
+        With<MeeleeAttackRequest>,
+        With<RangedAttackRequest>,
+        With<CastSpellRequest>,
+    )>,
+>;
+
+/// Fighting is loud, every attack and spell makes a [Noise] where the attacker stands
+fn make_combat_noise(mut noises: EventWriter<Noise>, combatants: Combatants) {
+    for (source, origin) in combatants.iter() {
+        noises.send(Noise {
+            source,
+            origin: *origin,
+            volume: COMBAT_VOLUME,
+        });
+    }
+}
+
+/// Worn equipment with the bonuses it grants
+type EquipmentBonuses<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Equipped,
+        Option<&'static PowerBonus>,
+        Option<&'static DefenseBonus>,
+    ),
+>;
+
 fn combat_system(
     mut cmd: Commands,
     mut log_event_writer: EventWriter<LogMessage>,
     attackers: Query<(Entity, &Name, &MeeleeAttackRequest, &Power)>,
     mut targets: Query<(&Name, &mut SufferDamage, &Health, &Defense)>,
+    on_hit: Query<&InflictsOnHit>,
+    mut status_effects: Query<&mut StatusEffects>,
+    equipment: EquipmentBonuses,
 ) {
-    fn process_attack(
-        log_event_writer: &mut EventWriter<LogMessage>,
-        mut suffer_damage: Mut<SufferDamage>,
-        health: &Health,
-        power: &Power,
-        defense: &Defense,
-        attacker_name: &Name,
-        target_name: &Name,
-    ) {
-        if health.current < health.min {
-            return;
-        }
-
-        let damage = i32::max(0, power.0 - defense.0);
-
-        if damage == 0 {
-            debug!(%attacker_name, %target_name, "failed to apply damage to target");
-        }
-
-        debug!(%attacker_name, %target_name, %damage, "attack success");
-        suffer_damage.add_damage(damage);
-        log_event_writer.send(LogMessage::AttackMessage {
-            time: chrono::Local::now(),
-            attacker: attacker_name.clone(),
-            defender: target_name.clone(),
-            damage,
-        });
-    }
-
     trace!(attackers = %attackers.iter().count(), "processing combat");
+    let mut rng = rand::thread_rng();
     for (entity, attacker_name, MeeleeAttackRequest { target }, power) in attackers.into_iter() {
         debug!(%attacker_name, ?target, "trying to attack");
         match targets.get_mut(*target) {
             Ok((target_name, suffer_damage, health, defense)) => {
-                process_attack(
+                let damage = process_attack(
                     &mut log_event_writer,
                     suffer_damage,
                     health,
-                    power,
-                    defense,
-                    attacker_name,
+                    effective_power(entity, power, &equipment),
+                    effective_defense(*target, defense, &equipment),
+                    (entity, attacker_name),
                     target_name,
                 );
+
+                if let (Ok(on_hit), Ok(mut effects)) =
+                    (on_hit.get(entity), status_effects.get_mut(*target))
+                {
+                    if damage > 0 && rng.gen_bool(on_hit.chance) {
+                        debug!(%attacker_name, %target_name, effect = ?on_hit.effect, "inflicted effect");
+                        effects.add(on_hit.effect);
+                    }
+                }
             }
             Err(err) => error!(%err, "failed to attack target"),
         }
@@ -78,26 +126,299 @@ fn combat_system(
     }
 }
 
-fn apply_damage(mut query: Query<(&mut Health, &mut SufferDamage)>) {
-    fn apply_damage((mut health, mut suffer_damage): (Mut<Health>, Mut<SufferDamage>)) {
-        health.take_damage(suffer_damage.drain().sum())
+/// Power of the `entity` including bonuses of the equipment it wears
+fn effective_power(entity: Entity, power: &Power, equipment: &EquipmentBonuses) -> i32 {
+    power.0 + total_bonuses(entity, equipment.iter()).0
+}
+
+/// Defense of the `entity` including bonuses of the equipment it wears
+fn effective_defense(entity: Entity, defense: &Defense, equipment: &EquipmentBonuses) -> i32 {
+    defense.0 + total_bonuses(entity, equipment.iter()).1
+}
+
+/// Computes damage dealt by attacker to the target, applies it and logs the attack. Returns the damage dealt.
+fn process_attack(
+    log_event_writer: &mut EventWriter<LogMessage>,
+    mut suffer_damage: Mut<SufferDamage>,
+    health: &Health,
+    power: i32,
+    defense: i32,
+    (attacker, attacker_name): (Entity, &Name),
+    target_name: &Name,
+) -> i32 {
+    if health.current < health.min {
+        return 0;
+    }
+
+    let damage = i32::max(0, power - defense);
+
+    if damage == 0 {
+        debug!(%attacker_name, %target_name, "failed to apply damage to target");
     }
 
-    query.iter_mut().for_each(apply_damage);
+    debug!(%attacker_name, %target_name, %damage, "attack success");
+    suffer_damage.add_damage(damage, Some(attacker));
+    log_event_writer.send(LogMessage::AttackMessage {
+        time: chrono::Local::now(),
+        attacker: attacker_name.clone(),
+        defender: target_name.clone(),
+        damage,
+    });
+    damage
 }
 
-fn delete_the_dead(
+/// Processes [RangedAttackRequest]s. Attack happens only if the target is within range and nothing that
+/// [BlocksSight] stands in the way.
+fn ranged_combat_system(
     mut cmd: Commands,
     mut log_event_writer: EventWriter<LogMessage>,
-    query: Query<(Entity, Option<&Name>, &Health), Without<Player>>,
+    attackers: Query<(Entity, &Name, &RangedAttackRequest, &Power, &Position)>,
+    mut targets: Query<(&Name, &mut SufferDamage, &Health, &Defense, &Position)>,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+    equipment: EquipmentBonuses,
 ) {
-    query.iter().for_each(|(entity, name, health)| {
-        if health.is_dead() {
-            cmd.entity(entity).despawn();
-            log_event_writer.send(LogMessage::Death {
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+
+    for (entity, attacker_name, RangedAttackRequest { target, range }, power, attacker_pos) in
+        attackers.iter()
+    {
+        debug!(%attacker_name, ?target, "trying to shoot");
+        cmd.entity(entity).remove::<RangedAttackRequest>();
+
+        let Ok((target_name, suffer_damage, health, defense, target_pos)) =
+            targets.get_mut(*target)
+        else {
+            error!(?target, "failed to shoot target");
+            continue;
+        };
+
+        if attacker_pos.distance(*target_pos) > *range {
+            debug!(%attacker_name, %target_name, "target is out of range");
+            continue;
+        }
+
+        if !has_line_of_fire(*attacker_pos, *target_pos, |pos| blockers.contains(pos)) {
+            debug!(%attacker_name, %target_name, "no clear line of fire");
+            continue;
+        }
+
+        process_attack(
+            &mut log_event_writer,
+            suffer_damage,
+            health,
+            effective_power(entity, power, &equipment),
+            effective_defense(*target, defense, &equipment),
+            (entity, attacker_name),
+            target_name,
+        );
+    }
+}
+
+/// Processes [CastSpellRequest]s. Bolts hurt their target the same way any other attack does, heals restore health
+/// and summoned monsters are spawned through [SpawnRequest].
+fn spell_system(
+    mut cmd: Commands,
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut spawn_requests: EventWriter<SpawnRequest>,
+    casters: Query<(Entity, &Name, &CastSpellRequest)>,
+    mut targets: Query<(&Name, &mut Health, &mut SufferDamage)>,
+) {
+    for (caster, caster_name, CastSpellRequest { spell, target }) in casters.iter() {
+        debug!(%caster_name, ?spell, ?target, "casting spell");
+        cmd.entity(caster).remove::<CastSpellRequest>();
+
+        let (target_name, damage) = match (spell, target) {
+            (Spell::Heal { amount }, SpellTarget::Entity(target)) => {
+                let Ok((target_name, mut health, _)) = targets.get_mut(*target) else {
+                    error!(?target, "failed to heal target");
+                    continue;
+                };
+                health.heal(*amount);
+                (Some(target_name.clone()), None)
+            }
+            (Spell::Bolt { damage, .. }, SpellTarget::Entity(target)) => {
+                let Ok((target_name, health, mut suffer_damage)) = targets.get_mut(*target) else {
+                    error!(?target, "failed to hit target with a bolt");
+                    continue;
+                };
+                if health.current < health.min {
+                    continue;
+                }
+                suffer_damage.add_damage(*damage, Some(caster));
+                (Some(target_name.clone()), Some(*damage))
+            }
+            (Spell::Summon { kind }, SpellTarget::Position(position)) => {
+                spawn_requests.send(SpawnRequest::Monster(*kind, *position));
+                (None, None)
+            }
+            _ => {
+                error!(?spell, ?target, "spell cast at a wrong kind of target");
+                continue;
+            }
+        };
+
+        log_event_writer.send(LogMessage::SpellCast {
+            time: chrono::Local::now(),
+            caster: caster_name.clone(),
+            spell: *spell,
+            target: target_name.clone(),
+        });
+        if let (Some(defender), Some(damage)) = (target_name, damage) {
+            log_event_writer.send(LogMessage::AttackMessage {
                 time: chrono::Local::now(),
-                name: name.map(Clone::clone).unwrap_or(Name::new("Unnamed")),
+                attacker: caster_name.clone(),
+                defender,
+                damage,
             });
         }
-    });
+    }
+}
+
+/// Applies the damage suffered this frame. Monsters which got hurt, but survived, may complain about it.
+fn apply_damage(
+    mut barks: EventWriter<CurseAtPlayer>,
+    mut query: Query<(Entity, &mut Health, &mut SufferDamage)>,
+) {
+    for (entity, mut health, mut suffer_damage) in query.iter_mut() {
+        let damage: i32 = suffer_damage.drain().sum();
+        if damage == 0 {
+            continue;
+        }
+        health.take_damage(damage);
+        // only monsters have lines to bark, so the player getting hurt is ignored later on
+        if !health.is_dead() {
+            barks.send(CurseAtPlayer {
+                monster: entity,
+                trigger: BarkTrigger::Wounded,
+            });
+        }
+    }
+}
+
+/// Triggers [OnDeath] effects of entities that have just died
+fn trigger_death_effects(
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut spawn_requests: EventWriter<SpawnRequest>,
+    dead: Query<(&Name, &Position, &Health, &OnDeath)>,
+    blocks_tile: Query<&Position, With<BlocksTile>>,
+    blast_blockers: Query<&Position, (With<BlocksSight>, Without<Monster>)>,
+    mut targets: Query<(&Name, &Position, &mut SufferDamage)>,
+) {
+    for (name, position, _, OnDeath(effects)) in
+        dead.iter().filter(|(_, _, health, _)| health.is_dead())
+    {
+        for effect in effects {
+            debug!(%name, ?effect, "triggering death effect");
+            match effect {
+                DeathEffect::Explode { radius, damage } => {
+                    let blockers = blast_blockers.iter().collect::<HashSet<&Position>>();
+                    let area = blast_area(*position, *radius, |pos| blockers.contains(pos));
+                    targets
+                        .iter_mut()
+                        .filter(|(_, pos, _)| *pos != position && area.contains(*pos))
+                        .for_each(|(target_name, _, mut suffer_damage)| {
+                            suffer_damage.add_damage(*damage, None);
+                            log_event_writer.send(LogMessage::AttackMessage {
+                                time: chrono::Local::now(),
+                                attacker: name.clone(),
+                                defender: target_name.clone(),
+                                damage: *damage,
+                            });
+                        });
+                }
+                DeathEffect::Split { into, count } => {
+                    // tiles that are blocked or where some other creature already stands
+                    let occupied = blocks_tile
+                        .iter()
+                        .chain(targets.iter().map(|(_, pos, _)| pos))
+                        .collect::<HashSet<&Position>>();
+                    position
+                        .possible_successors()
+                        .into_iter()
+                        .filter(|pos| !occupied.contains(pos))
+                        .take(*count as usize)
+                        .for_each(|pos| {
+                            spawn_requests.send(SpawnRequest::Monster(*into, pos));
+                        });
+                }
+                DeathEffect::DropLoot(loot_table) => {
+                    loot_table.roll().into_iter().for_each(|kind| {
+                        spawn_requests.send(SpawnRequest::Item(kind, *position));
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Replaces dead monsters with their corpses and drops everything they carried in their backpacks
+fn leave_remains(
+    mut cmd: Commands,
+    dead: Query<(Entity, &Name, &Position, &Health), With<Monster>>,
+    textures: Query<&Handle<Image>>,
+    backpacks: Query<(Entity, &InBackpack)>,
+) {
+    for (entity, name, position, _) in dead.iter().filter(|(.., health)| health.is_dead()) {
+        if let Ok(texture) = textures.get(entity) {
+            let corpse_position = Position::new(position.x, position.y, CORPSE_Z as i32);
+            cmd.spawn((
+                SpriteBundle {
+                    texture: texture.clone(),
+                    // becomes visible once player's field of view is updated
+                    visibility: Visibility::Hidden,
+                    sprite: Sprite {
+                        color: Color::rgb(0.5, 0.2, 0.2),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(corpse_position.into())
+                        .with_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+                    ..default()
+                },
+                corpse_position,
+                Name::new(format!("{name} corpse")),
+                Corpse,
+            ));
+        }
+
+        backpacks
+            .iter()
+            .filter(|(_, in_backpack)| in_backpack.owner == entity)
+            .for_each(|(item, _)| {
+                debug!(%name, ?item, "dropping carried item");
+                cmd.entity(item)
+                    .remove::<InBackpack>()
+                    .insert(Position::new(position.x, position.y, ITEM_Z as i32));
+            });
+    }
+}
+
+fn delete_the_dead(
+    mut cmd: Commands,
+    mut log_event_writer: EventWriter<LogMessage>,
+    query: Query<(Entity, Option<&Name>, &Health, &SufferDamage), Without<Player>>,
+    xp_values: Query<&XpValue>,
+    mut killers: Query<&mut Experience>,
+) {
+    query
+        .iter()
+        .for_each(|(entity, name, health, suffer_damage)| {
+            if health.is_dead() {
+                // speech bubbles are children of the monster, they go together with it
+                cmd.entity(entity).despawn_recursive();
+                log_event_writer.send(LogMessage::Death {
+                    time: chrono::Local::now(),
+                    name: name.map(Clone::clone).unwrap_or(Name::new("Unnamed")),
+                });
+
+                // reward whoever dealt the killing blow
+                if let (Ok(XpValue(xp)), Some(killer)) =
+                    (xp_values.get(entity), suffer_damage.last_source)
+                {
+                    if let Ok(mut experience) = killers.get_mut(killer) {
+                        debug!(?killer, %xp, "gained experience");
+                        experience.gain(*xp);
+                    }
+                }
+            }
+        });
 }
@@ -0,0 +1,37 @@
+//! Hunger related systems
+//!
+//!
+
+use crate::{
+    components::{
+        combat::SufferDamage,
+        hunger::{Hunger, HungerState},
+        Name,
+    },
+    states::GameState,
+};
+use bevy::prelude::*;
+
+/// Damage starving entity suffers each turn
+const STARVATION_DAMAGE: i32 = 1;
+
+pub struct HungerPlugin;
+
+impl Plugin for HungerPlugin {
+    fn build(&self, app: &mut App) {
+        // same as status effects, hunger grows once per round
+        app.add_systems(OnExit(GameState::EnemyTurn), tick_hunger);
+    }
+}
+
+/// Makes everyone a bit hungrier and hurts those who are starving
+fn tick_hunger(mut query: Query<(Option<&Name>, &mut Hunger, &mut SufferDamage)>) {
+    for (name, mut hunger, mut suffer_damage) in query.iter_mut() {
+        hunger.tick();
+
+        if hunger.state() == HungerState::Starving {
+            trace!(?name, "starving");
+            suffer_damage.add_damage(STARVATION_DAMAGE, None);
+        }
+    }
+}
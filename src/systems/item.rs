@@ -0,0 +1,464 @@
+//! Item related systems, picking items up and using them
+//!
+//!
+
+use super::targeting::blast_area;
+use crate::{
+    components::{
+        combat::{Health, SufferDamage},
+        equipment::{Cursed, DefenseBonus, Equippable, Equipped, PowerBonus},
+        hunger::Hunger,
+        item::{Food, GrantsEffect, InBackpack, Item, MagicItemKind, Potion, Scroll},
+        light::{LampOil, Lantern},
+        requests::UseItemRequest,
+        shop::{GoldPile, Wallet},
+        status::{StatusEffect, StatusEffectKind, StatusEffects},
+        BlocksSight, FogOfWar, Monster, Name, Player, Position, Revealed, Visible,
+    },
+    consts::FOW_ALPHA,
+    resources::ItemIdentities,
+    states::GameState,
+    ui::log::LogMessage,
+};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Items lying somewhere on the level, not carried by anyone
+type LevelItems<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut Visibility, &'static mut Sprite),
+    (With<Item>, With<Position>),
+>;
+
+pub(super) struct ItemPlugin;
+
+impl Plugin for ItemPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ItemIdentities::random()).add_systems(
+            Update,
+            (
+                pick_up_item.run_if(in_state(GameState::PlayerTurn)),
+                collect_gold,
+                (
+                    drink_potion,
+                    grant_item_effects,
+                    eat_food,
+                    refill_lantern,
+                    read_area_scroll,
+                    read_magic_mapping,
+                    read_telepathy,
+                    read_detect_items,
+                    read_remove_curse,
+                    equip_item,
+                    reveal_curses,
+                    identify_used_items,
+                    consume_used_items,
+                )
+                    .chain(),
+            ),
+        );
+    }
+}
+
+/// Using a magic item reveals what it is, from then on all items of the same kind are shown under the real name
+fn identify_used_items(
+    mut identities: ResMut<ItemIdentities>,
+    users: Query<&UseItemRequest>,
+    mut items: Query<(&MagicItemKind, &mut Name)>,
+) {
+    for UseItemRequest { item, .. } in users.iter() {
+        let Ok((kind, _)) = items.get(*item) else {
+            continue;
+        };
+        let kind = *kind;
+        if !identities.identify(kind) {
+            continue;
+        }
+
+        debug!(?kind, "identified");
+        items
+            .iter_mut()
+            .filter(|(other, _)| **other == kind)
+            .for_each(|(_, mut name)| *name = Name::new(kind.real_name()));
+    }
+}
+
+/// Picks up an item lying on the same tile as the player and puts it into player's backpack
+fn pick_up_item(
+    mut cmd: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut log_event_writer: EventWriter<LogMessage>,
+    input: Res<ButtonInput<KeyCode>>,
+    player: Query<(Entity, &Name, &Position), With<Player>>,
+    mut items: Query<(Entity, &Name, &Position, &mut Visibility), With<Item>>,
+) {
+    if !input.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+
+    let (player_ent, player_name, player_pos) = player.single();
+    let Some((item, item_name, _, mut visibility)) =
+        items.iter_mut().find(|(_, _, pos, _)| *pos == player_pos)
+    else {
+        debug!("there is nothing to pick up");
+        return;
+    };
+
+    *visibility = Visibility::Hidden;
+    cmd.entity(item)
+        .remove::<(Position, Visible)>()
+        .insert(InBackpack::new(player_ent));
+
+    log_event_writer.send(LogMessage::ItemPickedUp {
+        time: chrono::Local::now(),
+        name: player_name.clone(),
+        item: item_name.clone(),
+    });
+    next_state.set(GameState::EnemyTurn);
+}
+
+/// Player picks up gold just by stepping on it
+fn collect_gold(
+    mut cmd: Commands,
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut player: Query<(&Name, &Position, &mut Wallet), With<Player>>,
+    piles: Query<(Entity, &Name, &Position, &GoldPile)>,
+) {
+    let Ok((player_name, player_pos, mut wallet)) = player.get_single_mut() else {
+        return;
+    };
+
+    for (pile, pile_name, _, GoldPile(amount)) in
+        piles.iter().filter(|(_, _, pos, _)| *pos == player_pos)
+    {
+        wallet.earn(*amount);
+        cmd.entity(pile).despawn();
+        log_event_writer.send(LogMessage::ItemPickedUp {
+            time: chrono::Local::now(),
+            name: player_name.clone(),
+            item: pile_name.clone(),
+        });
+    }
+}
+
+fn drink_potion(mut users: Query<(&UseItemRequest, &mut Health)>, potions: Query<&Potion>) {
+    for (UseItemRequest { item, .. }, mut health) in users.iter_mut() {
+        if let Ok(Potion { amount }) = potions.get(*item) {
+            debug!(%amount, "drinking potion");
+            health.heal(*amount);
+        }
+    }
+}
+
+fn grant_item_effects(
+    mut users: Query<(&UseItemRequest, &mut StatusEffects)>,
+    items: Query<&GrantsEffect>,
+) {
+    for (UseItemRequest { item, .. }, mut effects) in users.iter_mut() {
+        if let Ok(GrantsEffect(effect)) = items.get(*item) {
+            debug!(?effect, "granting effect");
+            effects.add(*effect);
+        }
+    }
+}
+
+fn eat_food(mut users: Query<(&UseItemRequest, &mut Hunger)>, food: Query<&Food>) {
+    for (UseItemRequest { item, .. }, mut hunger) in users.iter_mut() {
+        if let Ok(Food { nutrition }) = food.get(*item) {
+            debug!(%nutrition, "eating");
+            hunger.eat(*nutrition);
+        }
+    }
+}
+
+fn refill_lantern(mut users: Query<(&UseItemRequest, &mut Lantern)>, oil: Query<&LampOil>) {
+    for (UseItemRequest { item, .. }, mut lantern) in users.iter_mut() {
+        if let Ok(LampOil { fuel }) = oil.get(*item) {
+            debug!(%fuel, "refilling lantern");
+            lantern.refill(*fuel);
+        }
+    }
+}
+
+/// Applies effects of scrolls which affect everything within an area around the target
+fn read_area_scroll(
+    mut log_event_writer: EventWriter<LogMessage>,
+    users: Query<(Entity, &Name, &UseItemRequest)>,
+    scrolls: Query<&Scroll>,
+    blast_blockers: Query<&Position, (With<BlocksSight>, Without<Monster>)>,
+    mut targets: Query<(
+        &Position,
+        &Name,
+        &mut SufferDamage,
+        &mut StatusEffects,
+        Has<Monster>,
+    )>,
+) {
+    for (user, user_name, UseItemRequest { item, target }) in users.iter() {
+        let (Ok(scroll), Some(target)) = (scrolls.get(*item), target) else {
+            continue;
+        };
+        let Some(radius) = scroll.radius() else {
+            continue;
+        };
+
+        let blockers = blast_blockers.iter().collect::<HashSet<&Position>>();
+        let area = blast_area(*target, radius, |pos| blockers.contains(pos));
+
+        targets
+            .iter_mut()
+            .filter(|(pos, ..)| area.contains(*pos))
+            .for_each(
+                |(_, target_name, mut suffer_damage, mut effects, is_monster)| match scroll {
+                    Scroll::Fireball { damage, .. } => {
+                        suffer_damage.add_damage(*damage, Some(user));
+                        log_event_writer.send(LogMessage::AttackMessage {
+                            time: chrono::Local::now(),
+                            attacker: user_name.clone(),
+                            defender: target_name.clone(),
+                            damage: *damage,
+                        });
+                    }
+                    Scroll::Confusion { turns, .. } if is_monster => {
+                        debug!(%target_name, "confused");
+                        effects.add(StatusEffect::new(StatusEffectKind::Confusion, *turns));
+                    }
+                    Scroll::Confusion { .. }
+                    | Scroll::MagicMapping
+                    | Scroll::RemoveCurse
+                    | Scroll::Telepathy { .. }
+                    | Scroll::DetectItems => (),
+                },
+            );
+    }
+}
+
+/// Reveals all walls and floors of the map, those are the entities covered by [FogOfWar]. Tiles which were never
+/// seen by the player are the ones still hidden.
+fn read_magic_mapping(
+    mut cmd: Commands,
+    users: Query<&UseItemRequest>,
+    scrolls: Query<&Scroll>,
+    mut tiles: Query<(Entity, &mut Visibility, &mut Sprite), With<FogOfWar>>,
+) {
+    if !users
+        .iter()
+        .any(|request| matches!(scrolls.get(request.item), Ok(Scroll::MagicMapping)))
+    {
+        return;
+    }
+
+    debug!("revealing the map");
+    tiles
+        .iter_mut()
+        .filter(|(_, visibility, _)| matches!(**visibility, Visibility::Hidden))
+        .for_each(|(entity, mut visibility, mut sprite)| {
+            cmd.entity(entity).insert(Revealed);
+            *visibility = Visibility::Visible;
+            sprite.color.set_a(FOW_ALPHA);
+        });
+}
+
+/// Makes the reader telepathic, monsters within the radius become visible even when out of sight
+fn read_telepathy(
+    mut readers: Query<(&Name, &UseItemRequest, &mut StatusEffects)>,
+    scrolls: Query<&Scroll>,
+) {
+    for (name, UseItemRequest { item, .. }, mut effects) in readers.iter_mut() {
+        let Ok(Scroll::Telepathy { turns, radius }) = scrolls.get(*item) else {
+            continue;
+        };
+
+        debug!(%name, %radius, "telepathic");
+        effects.add(StatusEffect::new(
+            StatusEffectKind::Telepathy { radius: *radius },
+            *turns,
+        ));
+    }
+}
+
+/// Reveals all the items lying on the level. From then on they are covered by [FogOfWar] same as the map, so
+/// they stay shown where they were detected even when out of sight.
+fn read_detect_items(
+    mut cmd: Commands,
+    users: Query<&UseItemRequest>,
+    scrolls: Query<&Scroll>,
+    mut items: LevelItems,
+) {
+    if !users
+        .iter()
+        .any(|request| matches!(scrolls.get(request.item), Ok(Scroll::DetectItems)))
+    {
+        return;
+    }
+
+    debug!("detecting items");
+    items
+        .iter_mut()
+        .for_each(|(entity, mut visibility, mut sprite)| {
+            cmd.entity(entity).insert((Revealed, FogOfWar));
+            if matches!(*visibility, Visibility::Hidden) {
+                *visibility = Visibility::Visible;
+                sprite.color.set_a(FOW_ALPHA);
+            }
+        });
+}
+
+/// Lifts curses from all the items carried or worn by the reader of the scroll, restoring their bonuses
+fn read_remove_curse(
+    mut cmd: Commands,
+    users: Query<(Entity, &UseItemRequest)>,
+    scrolls: Query<&Scroll>,
+    mut cursed: Query<(Entity, &mut Name), With<Cursed>>,
+    mut bonuses: Query<(&mut PowerBonus, &mut DefenseBonus)>,
+    carried: Query<(Option<&InBackpack>, Option<&Equipped>)>,
+) {
+    for (user, UseItemRequest { item, .. }) in users.iter() {
+        if !matches!(scrolls.get(*item), Ok(Scroll::RemoveCurse)) {
+            continue;
+        }
+
+        for (cursed_item, mut name) in cursed.iter_mut() {
+            let Ok((in_backpack, equipped)) = carried.get(cursed_item) else {
+                continue;
+            };
+            let carried = in_backpack.is_some_and(|in_backpack| in_backpack.owner == user)
+                || equipped.is_some_and(|equipped| equipped.owner == user);
+            if !carried {
+                continue;
+            }
+
+            debug!(name = %name.0, "curse lifted");
+            if let Some(uncursed) = name.0.strip_suffix(Cursed::NAME_SUFFIX) {
+                *name = Name::new(uncursed);
+            }
+            // cursed items carry their bonuses reversed, see `EquipmentKind::bonuses`
+            if let Ok((mut power, mut defense)) = bonuses.get_mut(cursed_item) {
+                power.0 = -power.0;
+                defense.0 = -defense.0;
+            }
+            cmd.entity(cursed_item).remove::<Cursed>();
+        }
+    }
+}
+
+/// Puts on an equippable item, or takes it off if it is already worn. If the slot is already full, the item worn
+/// in the slot is moved back to the backpack. Cursed items, once worn, stay on.
+fn equip_item(
+    mut cmd: Commands,
+    mut log_event_writer: EventWriter<LogMessage>,
+    users: Query<(Entity, &Name, &UseItemRequest)>,
+    equippable: Query<(&Name, &Equippable, Option<&Equipped>, Has<Cursed>)>,
+    worn: Query<(Entity, &Name, &Equipped, Has<Cursed>)>,
+) {
+    for (user, user_name, UseItemRequest { item, .. }) in users.iter() {
+        let Ok((item_name, Equippable { slot }, equipped, cursed)) = equippable.get(*item) else {
+            continue;
+        };
+
+        if equipped.is_some() && cursed {
+            log_event_writer.send(LogMessage::ItemCursed {
+                time: chrono::Local::now(),
+                name: user_name.clone(),
+                item: item_name.clone(),
+            });
+            continue;
+        }
+
+        if equipped.is_some() {
+            cmd.entity(*item)
+                .remove::<Equipped>()
+                .insert(InBackpack::new(user));
+            log_event_writer.send(LogMessage::ItemUnequipped {
+                time: chrono::Local::now(),
+                name: user_name.clone(),
+                item: item_name.clone(),
+            });
+            continue;
+        }
+
+        let worn_in_slot = worn
+            .iter()
+            .filter(|(_, _, equipped, _)| equipped.owner == user && equipped.slot == *slot)
+            .collect::<Vec<_>>();
+        if worn_in_slot.len() >= slot.capacity() {
+            let Some((worn_item, worn_name, ..)) =
+                worn_in_slot.iter().find(|(.., cursed)| !cursed).copied()
+            else {
+                // everything worn in the slot is cursed, so there is no room for the item
+                log_event_writer.send(LogMessage::ItemCursed {
+                    time: chrono::Local::now(),
+                    name: user_name.clone(),
+                    item: worn_in_slot[0].1.clone(),
+                });
+                continue;
+            };
+            cmd.entity(worn_item)
+                .remove::<Equipped>()
+                .insert(InBackpack::new(user));
+            log_event_writer.send(LogMessage::ItemUnequipped {
+                time: chrono::Local::now(),
+                name: user_name.clone(),
+                item: worn_name.clone(),
+            });
+        }
+
+        cmd.entity(*item)
+            .remove::<InBackpack>()
+            .insert(Equipped::new(user, *slot));
+        log_event_writer.send(LogMessage::ItemEquipped {
+            time: chrono::Local::now(),
+            name: user_name.clone(),
+            item: item_name.clone(),
+        });
+    }
+}
+
+/// Curse of a freshly equipped item reveals itself
+fn reveal_curses(
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut items: Query<(&Equipped, &mut Cursed, &mut Name), Added<Equipped>>,
+    owners: Query<&Name, Without<Cursed>>,
+) {
+    for (equipped, mut cursed, mut name) in items.iter_mut() {
+        if cursed.revealed {
+            continue;
+        }
+
+        cursed.revealed = true;
+        *name = Name::new(format!("{}{}", name.0, Cursed::NAME_SUFFIX));
+        debug!(name = %name.0, "curse revealed");
+
+        if let Ok(owner_name) = owners.get(equipped.owner) {
+            log_event_writer.send(LogMessage::ItemCursed {
+                time: chrono::Local::now(),
+                name: owner_name.clone(),
+                item: name.clone(),
+            });
+        }
+    }
+}
+
+/// Logs the use of an item and despawns it, as all the usable items are single use. Equipment is not used up,
+/// it only gets worn.
+fn consume_used_items(
+    mut cmd: Commands,
+    mut log_event_writer: EventWriter<LogMessage>,
+    users: Query<(Entity, &Name, &UseItemRequest)>,
+    items: Query<(&Name, Has<Equippable>), With<Item>>,
+) {
+    for (user, user_name, UseItemRequest { item, .. }) in users.iter() {
+        cmd.entity(user).remove::<UseItemRequest>();
+
+        let Ok((item_name, false)) = items.get(*item) else {
+            continue;
+        };
+
+        log_event_writer.send(LogMessage::ItemUsed {
+            time: chrono::Local::now(),
+            name: user_name.clone(),
+            item: item_name.clone(),
+        });
+        cmd.entity(*item).despawn();
+    }
+}
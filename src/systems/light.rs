@@ -0,0 +1,162 @@
+//! Lighting. Every [LightSource] lights up the tiles it can see, using the same field of view algorithm as the
+//! viewers. Sprites are tinted by the colour and intensity of the light falling on them, tiles no light reaches are
+//! drawn darker. Monsters standing in the dark can only be seen from up close, see
+//! [update_visibility](super::player).
+
+use super::{map::Map, vision::euclidean_distance};
+use crate::{
+    algorithms::fov::FovMode,
+    components::{
+        light::{BaseColor, Lantern, LightSource},
+        BlocksSight, Name, Position, Visible,
+    },
+    resources::LightMap,
+    states::GameState,
+    ui::log::LogMessage,
+};
+use bevy::{prelude::*, utils::HashSet};
+
+/// How bright a tile no light reaches is, relative to a fully lit one
+const AMBIENT_LIGHT: f32 = 0.35;
+
+/// Sprites which are tinted by the light falling on them
+type LitSprites<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Ref<'static, Position>,
+        Ref<'static, BaseColor>,
+        Ref<'static, Visible>,
+        &'static mut Sprite,
+    ),
+>;
+
+/// Sprites on the map which have not been tinted yet
+type UntintedSprites<'w, 's> =
+    Query<'w, 's, (Entity, &'static Sprite), (With<Position>, Without<BaseColor>)>;
+
+pub(super) struct LightPlugin;
+
+impl Plugin for LightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LightMap>()
+            .add_systems(Update, light_lanterns)
+            // same as hunger, lanterns burn fuel once per round
+            .add_systems(OnExit(GameState::EnemyTurn), burn_lantern_fuel);
+    }
+}
+
+/// Recomputes the [LightMap] whenever a light or something blocking it has moved. Light does not pass through
+/// walls, not even through those hidden in solid rock which are never spawned.
+pub(super) fn compute_light_map(
+    mut light_map: ResMut<LightMap>,
+    map: Res<Map>,
+    lights: Query<(Ref<Position>, Ref<LightSource>)>,
+    mut removed_lights: RemovedComponents<LightSource>,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+    changed_blockers: Query<(), (With<BlocksSight>, Changed<Position>)>,
+) {
+    let removed = removed_lights.read().count() > 0;
+    let changed = lights
+        .iter()
+        .any(|(position, light)| position.is_changed() || light.is_changed());
+    if !changed && changed_blockers.is_empty() && !removed {
+        return;
+    }
+
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+    light_map.clear();
+
+    for (position, light) in lights.iter() {
+        let lit_tiles = FovMode::Permissive.compute(
+            *position,
+            light.radius,
+            |x, y| {
+                let tile = Position::new(x, y, position.z);
+                !map.is_floor(&tile) || blockers.contains(&tile)
+            },
+            |x, y| euclidean_distance(0, 0, x, y),
+        );
+
+        for tile in lit_tiles {
+            let intensity = light.intensity_at(position.distance(tile));
+            light_map.add(
+                tile,
+                Color::rgb(
+                    light.color.r() * intensity,
+                    light.color.g() * intensity,
+                    light.color.b() * intensity,
+                ),
+            );
+        }
+    }
+}
+
+/// Tinting overwrites the colour of the sprite, so the original one is kept aside
+pub(super) fn remember_base_colors(mut cmd: Commands, sprites: UntintedSprites) {
+    for (entity, sprite) in sprites.iter() {
+        cmd.entity(entity).insert(BaseColor(sprite.color));
+    }
+}
+
+/// Tints sprites in the player's field of view by the light falling on them. Only the colour is changed, the alpha
+/// is left to the fog of war.
+pub(super) fn apply_lighting(light_map: Res<LightMap>, mut sprites: LitSprites) {
+    for (position, base_color, visible, mut sprite) in sprites.iter_mut() {
+        if !light_map.is_changed()
+            && !position.is_changed()
+            && !base_color.is_added()
+            && !visible.is_added()
+        {
+            continue;
+        }
+
+        let light = light_map.light_at(&position).unwrap_or(Color::BLACK);
+        let tint = |base: f32, light: f32| base * (AMBIENT_LIGHT + (1. - AMBIENT_LIGHT) * light);
+        let BaseColor(base) = *base_color;
+        let alpha = sprite.color.a();
+        sprite.color = Color::rgba(
+            tint(base.r(), light.r()),
+            tint(base.g(), light.g()),
+            tint(base.b(), light.b()),
+            alpha,
+        );
+    }
+}
+
+/// Keeps the light of each lantern in line with how much fuel it has left
+fn light_lanterns(mut cmd: Commands, lanterns: Query<(Entity, &Lantern), Changed<Lantern>>) {
+    for (entity, lantern) in lanterns.iter() {
+        match lantern.light() {
+            Some(light) => cmd.entity(entity).insert(light),
+            None => cmd.entity(entity).remove::<LightSource>(),
+        };
+    }
+}
+
+/// Lanterns burn their fuel, the owner is warned once the fuel runs low and when the lantern goes out
+fn burn_lantern_fuel(
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut lanterns: Query<(&Name, &mut Lantern)>,
+) {
+    for (name, mut lantern) in lanterns.iter_mut() {
+        if lantern.fuel == 0 {
+            continue;
+        }
+        let was_low = lantern.is_low();
+        lantern.tick();
+
+        if lantern.fuel == 0 {
+            debug!(%name, "lantern went out");
+            log_event_writer.send(LogMessage::LanternOut {
+                time: chrono::Local::now(),
+                name: name.clone(),
+            });
+        } else if lantern.is_low() && !was_low {
+            log_event_writer.send(LogMessage::LanternFlickers {
+                time: chrono::Local::now(),
+                name: name.clone(),
+            });
+        }
+    }
+}
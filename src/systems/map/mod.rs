@@ -2,13 +2,14 @@ mod rect;
 mod spawner;
 
 use crate::{
-    components::Position,
-    consts::{PLAYER_Z, WALL_Z},
+    components::{item::ItemKind, monster::MonsterKind, Position},
+    consts::{ITEM_Z, MONSTER_Z, PLAYER_Z, WALL_Z},
+    resources::ItemIdentities,
 };
 use bevy::{
     asset::AssetServer,
     log::trace,
-    prelude::{Commands, Res, Resource},
+    prelude::{Commands, Event, EventReader, Res, Resource},
 };
 use rand::Rng;
 use rect::Rect;
@@ -24,6 +25,13 @@ enum TileType {
     Floor,
 }
 
+/// Requests spawning of an entity once the map already exists, e.g. loot dropped by a dead monster
+#[derive(Debug, Clone, Copy, Event)]
+pub enum SpawnRequest {
+    Monster(MonsterKind, Position),
+    Item(ItemKind, Position),
+}
+
 /// We generate map using this struct and then spawn the map as entities in our ECS.
 #[derive(Debug, Clone, Resource)]
 pub struct Map {
@@ -39,6 +47,16 @@ impl Map {
         ((y * self.width) + x) as usize
     }
 
+    /// Whether there is a floor at the `position`, positions outside of the map are never floors
+    pub fn is_floor(&self, position: &Position) -> bool {
+        let (Ok(x), Ok(y)) = (usize::try_from(position.x), usize::try_from(position.y)) else {
+            return false;
+        };
+        x < self.width
+            && y < self.height
+            && matches!(self.tiles[self.xy_idx(x, y)], TileType::Floor)
+    }
+
     /// Converts index to x y coordinates
     pub fn idx_xy(&self, idx: usize) -> (usize, usize) {
         (idx % self.width, idx / self.width)
@@ -194,8 +212,15 @@ impl Map {
     }
 }
 
+/// Chance that a room gets a torch
+const TORCH_CHANCE: f64 = 0.7;
+
 /// Iterates over all tiles in the map and spawns them as a ECS entity. Also inserts [SpawnPoints] as a resource
-pub(super) fn spawn(mut cmd: Commands, asset_server: Res<AssetServer>) {
+pub(super) fn spawn(
+    mut cmd: Commands,
+    asset_server: Res<AssetServer>,
+    identities: Res<ItemIdentities>,
+) {
     let floor = asset_server.load("cave_floor_dark.png");
     let wall = asset_server.load("wall.png");
 
@@ -234,7 +259,68 @@ pub(super) fn spawn(mut cmd: Commands, asset_server: Res<AssetServer>) {
 
     spawn_player(&mut cmd, player_spawn_pos, &asset_server);
 
-    map.rooms.iter().skip(1).for_each(|room| {
-        populate_room(&mut cmd, room, 4, 2, &asset_server);
-    });
+    // most rooms have a torch on the wall above them, unless a tunnel goes through there
+    for room in map.rooms.iter() {
+        let (x, _) = room.center();
+        let torch = Position::new(x as i32, room.y2 as i32 + 1, ITEM_Z as i32);
+        if rand::thread_rng().gen_bool(TORCH_CHANCE) && !map.is_floor(&torch) {
+            spawn_torch(&mut cmd, torch);
+        }
+    }
+
+    // some dungeons have a shop, vendor stands in the middle of a room free of monsters
+    let shop_room = rand::thread_rng()
+        .gen_bool(0.5)
+        .then(|| rand::thread_rng().gen_range(1..map.rooms.len().max(2)));
+
+    map.rooms
+        .iter()
+        .enumerate()
+        .skip(1)
+        .for_each(|(index, room)| match Some(index) == shop_room {
+            true => {
+                let (x, y) = room.center();
+                spawn_vendor(
+                    &mut cmd,
+                    Position::new(x as i32, y as i32, MONSTER_Z as i32),
+                    &identities,
+                    &asset_server,
+                );
+                populate_room(&mut cmd, room, 0, 2, &identities, &asset_server);
+            }
+            false => populate_room(&mut cmd, room, 4, 2, &identities, &asset_server),
+        });
+
+    cmd.insert_resource(map);
+}
+
+/// Spawns entities requested by [SpawnRequest] events
+pub(super) fn process_spawn_requests(
+    mut cmd: Commands,
+    mut requests: EventReader<SpawnRequest>,
+    asset_server: Res<AssetServer>,
+    identities: Res<ItemIdentities>,
+) {
+    for request in requests.read() {
+        trace!(?request, "spawning requested entity");
+        match *request {
+            SpawnRequest::Monster(kind, Position { x, y, .. }) => {
+                spawn_monster_of_kind(
+                    &mut cmd,
+                    kind,
+                    Position::new(x, y, MONSTER_Z as i32),
+                    &asset_server,
+                );
+            }
+            SpawnRequest::Item(kind, Position { x, y, .. }) => {
+                spawn_item_of_kind(
+                    &mut cmd,
+                    kind,
+                    Position::new(x, y, ITEM_Z as i32),
+                    &identities,
+                    &asset_server,
+                );
+            }
+        }
+    }
 }
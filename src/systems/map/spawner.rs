@@ -1,32 +1,75 @@
 use super::rect::Rect;
 use crate::{
     ai::*,
+    algorithms::fov::FovMode,
     components::{bundles::*, *},
     consts::{FLOOR_Z, ITEM_Z, MONSTER_Z, SPRITE_SIZE},
+    resources::ItemIdentities,
 };
 use bevy::{
     prelude::{
-        default, AssetServer, Commands, Handle, Image, Res, SpriteBundle, Transform, Vec3,
-        Visibility,
+        default, AssetServer, Color, Commands, Entity, Handle, Image, Res, Sprite, SpriteBundle,
+        Transform, Vec3, Visibility,
     },
     utils::hashbrown::HashSet,
 };
 use big_brain::{pickers::FirstToScore, thinker::Thinker};
-use item::{Item, Potion};
+use equipment::{Cursed, DefenseBonus, EquipmentKind, EquipmentSlot, Equippable, PowerBonus};
+use faction::Faction;
+use item::{Food, GrantsEffect, InBackpack, Item, ItemKind, MagicItemKind, Potion, Scroll};
+use light::{LampOil, Lantern, LightSource};
+use monster::{DeathEffect, LootTable, MonsterKind, OnDeath, Pack, Sleeping};
 use rand::Rng;
+use shop::{GoldPile, Price, Vendor};
+use spell::{Spell, SpellKind, Spellbook};
+use status::{StatusEffect, StatusEffectKind};
+
+/// Chance that a monster spawned with the map is asleep
+const SLEEPING_CHANCE: f64 = 0.35;
 
 pub(super) fn spawn_monster(
     cmd: &mut Commands,
     position: Position,
     asset_server: &Res<AssetServer>,
 ) {
-    match (rand::thread_rng().gen_range(0f32..1f32) > 0.75f32) {
-        true => spawn_orc(cmd, position, asset_server.load("orc.png")),
-        false => spawn_goblin(cmd, position, asset_server.load("goblin.png")),
+    let mut rng = rand::thread_rng();
+    let kind = match rng.gen_range(0f32..1f32) {
+        roll if roll > 0.8f32 => MonsterKind::Orc,
+        roll if roll > 0.65f32 => MonsterKind::GoblinArcher,
+        roll if roll > 0.58f32 => MonsterKind::GoblinShaman,
+        roll if roll > 0.5f32 => MonsterKind::Slime,
+        roll if roll > 0.42f32 => MonsterKind::Rat,
+        _ => MonsterKind::Goblin,
+    };
+
+    let monster = spawn_monster_of_kind(cmd, kind, position, asset_server);
+    if rng.gen_bool(SLEEPING_CHANCE) {
+        cmd.entity(monster).insert(Sleeping);
+    }
+}
+
+pub(super) fn spawn_monster_of_kind(
+    cmd: &mut Commands,
+    kind: MonsterKind,
+    position: Position,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    match kind {
+        MonsterKind::Orc => spawn_orc(cmd, position, asset_server.load("orc.png")),
+        MonsterKind::Goblin => spawn_goblin(cmd, position, asset_server.load("goblin.png")),
+        MonsterKind::GoblinArcher => {
+            spawn_goblin_archer(cmd, position, asset_server.load("goblin.png"))
+        }
+        MonsterKind::GoblinShaman => {
+            spawn_goblin_shaman(cmd, position, asset_server.load("goblin.png"))
+        }
+        MonsterKind::Slime => spawn_slime(cmd, position, asset_server.load("orc.png")),
+        MonsterKind::SmallSlime => spawn_small_slime(cmd, position, asset_server.load("orc.png")),
+        MonsterKind::Rat => spawn_rat(cmd, position, asset_server.load("goblin.png")),
     }
 }
 
-pub(super) fn spawn_orc(cmd: &mut Commands, position: Position, texture: Handle<Image>) {
+pub(super) fn spawn_orc(cmd: &mut Commands, position: Position, texture: Handle<Image>) -> Entity {
     cmd.spawn((
         SpriteBundle {
             visibility: bevy::render::view::Visibility::Hidden,
@@ -34,19 +77,45 @@ pub(super) fn spawn_orc(cmd: &mut Commands, position: Position, texture: Handle<
             ..default()
         },
         position,
-        Viewshed::new(4),
+        Viewshed::new(4).with_mode(FovMode::Symmetric),
         Monster,
         BlocksSight,
         Name("Orc".into()),
+        MonsterKind::Orc,
+        Faction::Orcs,
+        Pack,
         CombatStats::new(16, 4, 1),
+        combat::InflictsOnHit::new(StatusEffect::new(StatusEffectKind::Stun, 2), 0.15),
+        experience::XpValue(50),
+        OnDeath(vec![DeathEffect::DropLoot(LootTable(vec![
+            (ItemKind::HealthPotion, 0.5),
+            (ItemKind::RegenerationPotion, 0.1),
+            (
+                ItemKind::Scroll(Scroll::Fireball {
+                    damage: 8,
+                    range: 6,
+                    radius: 2,
+                }),
+                0.1,
+            ),
+        ]))]),
         Thinker::build()
             .picker(FirstToScore { threshold: 0.5 })
-            .when(PlayerInAttackRange, MeeleeAttackPlayer)
-            .when(PlayerVisible, ChasePlayer),
-    ));
+            .when(Asleep, Sleep)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
 }
 
-pub(super) fn spawn_goblin(cmd: &mut Commands, position: Position, texture: Handle<Image>) {
+pub(super) fn spawn_goblin(
+    cmd: &mut Commands,
+    position: Position,
+    texture: Handle<Image>,
+) -> Entity {
     cmd.spawn((
         SpriteBundle {
             visibility: Visibility::Hidden,
@@ -54,16 +123,251 @@ pub(super) fn spawn_goblin(cmd: &mut Commands, position: Position, texture: Hand
             ..default()
         },
         position,
-        Viewshed::new(4),
+        Viewshed::new(4).with_mode(FovMode::Symmetric),
         Monster,
         BlocksSight,
         Name("Goblin".into()),
+        MonsterKind::Goblin,
+        Faction::Goblins,
+        Pack,
         CombatStats::new(16, 4, 1),
+        experience::XpValue(35),
+        OnDeath(vec![DeathEffect::DropLoot(LootTable(vec![(
+            ItemKind::HealthPotion,
+            0.2,
+        )]))]),
         Thinker::build()
             .picker(FirstToScore { threshold: 0.5 })
-            .when(PlayerInAttackRange, MeeleeAttackPlayer)
-            .when(PlayerVisible, ChasePlayer),
-    ));
+            .when(Asleep, Sleep)
+            .when(LowHealth, FleeFromPlayer)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
+}
+
+pub(super) fn spawn_goblin_archer(
+    cmd: &mut Commands,
+    position: Position,
+    texture: Handle<Image>,
+) -> Entity {
+    cmd.spawn((
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            texture,
+            // tinted, so it can be told apart from ordinary goblin
+            sprite: Sprite {
+                color: Color::rgb(0.7, 1., 0.7),
+                ..default()
+            },
+            ..default()
+        },
+        position,
+        Viewshed::new(6).with_mode(FovMode::Symmetric),
+        Monster,
+        BlocksSight,
+        Name("Goblin archer".into()),
+        MonsterKind::GoblinArcher,
+        Faction::Goblins,
+        Pack,
+        CombatStats::new(12, 3, 0),
+        combat::RangedAttack::new(5),
+        experience::XpValue(40),
+        Thinker::build()
+            .picker(FirstToScore { threshold: 0.5 })
+            .when(Asleep, Sleep)
+            .when(LowHealth, FleeFromPlayer)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(TargetInShootingRange, ShootTarget)
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
+}
+
+/// Goblin shaman stays behind the others, heals them, throws bolts and calls for more goblins
+pub(super) fn spawn_goblin_shaman(
+    cmd: &mut Commands,
+    position: Position,
+    texture: Handle<Image>,
+) -> Entity {
+    cmd.spawn((
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            texture,
+            sprite: Sprite {
+                color: Color::rgb(0.8, 0.6, 1.),
+                ..default()
+            },
+            ..default()
+        },
+        position,
+        Viewshed::new(6).with_mode(FovMode::Symmetric),
+        Monster,
+        BlocksSight,
+        Name("Goblin shaman".into()),
+        MonsterKind::GoblinShaman,
+        Faction::Goblins,
+        Pack,
+        CombatStats::new(10, 2, 0),
+        experience::XpValue(60),
+        Spellbook::new([
+            (Spell::Heal { amount: 6 }, 5),
+            (
+                Spell::Summon {
+                    kind: MonsterKind::Goblin,
+                },
+                25,
+            ),
+            (
+                Spell::Bolt {
+                    damage: 5,
+                    range: 6,
+                },
+                3,
+            ),
+        ]),
+        Thinker::build()
+            .picker(FirstToScore { threshold: 0.5 })
+            .when(Asleep, Sleep)
+            .when(LowHealth, FleeFromPlayer)
+            .when(CanCast(SpellKind::Heal), CastSpell(SpellKind::Heal))
+            .when(CanCast(SpellKind::Summon), CastSpell(SpellKind::Summon))
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(CanCast(SpellKind::Bolt), CastSpell(SpellKind::Bolt))
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
+}
+
+/// Slime splits into smaller slimes once killed
+pub(super) fn spawn_slime(
+    cmd: &mut Commands,
+    position: Position,
+    texture: Handle<Image>,
+) -> Entity {
+    cmd.spawn((
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            texture,
+            sprite: Sprite {
+                color: Color::rgb(0.4, 1., 0.4),
+                ..default()
+            },
+            ..default()
+        },
+        position,
+        Viewshed::new(3).with_mode(FovMode::Raycast),
+        LightSource::new(2, Color::rgb(0.4, 1., 0.4), 0.5),
+        Monster,
+        BlocksSight,
+        Name("Slime".into()),
+        MonsterKind::Slime,
+        Faction::Slimes,
+        CombatStats::new(20, 3, 0),
+        combat::InflictsOnHit::new(
+            StatusEffect::new(StatusEffectKind::Poison { damage: 1 }, 4),
+            0.5,
+        ),
+        experience::XpValue(30),
+        OnDeath(vec![DeathEffect::Split {
+            into: MonsterKind::SmallSlime,
+            count: 2,
+        }]),
+        Thinker::build()
+            .picker(FirstToScore { threshold: 0.5 })
+            .when(Asleep, Sleep)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
+}
+
+/// Small slime bursts in a splash of acid once killed. It is too small to block sight.
+pub(super) fn spawn_small_slime(
+    cmd: &mut Commands,
+    position: Position,
+    texture: Handle<Image>,
+) -> Entity {
+    cmd.spawn((
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            texture,
+            sprite: Sprite {
+                color: Color::rgb(0.6, 1., 0.4),
+                custom_size: Some(bevy::math::Vec2::splat(SPRITE_SIZE * 0.7)),
+                ..default()
+            },
+            ..default()
+        },
+        position,
+        Viewshed::new(3).with_mode(FovMode::Raycast),
+        LightSource::new(1, Color::rgb(0.6, 1., 0.4), 0.4),
+        Monster,
+        Name("Small slime".into()),
+        MonsterKind::SmallSlime,
+        Faction::Slimes,
+        CombatStats::new(6, 2, 0),
+        combat::InflictsOnHit::new(
+            StatusEffect::new(StatusEffectKind::Poison { damage: 1 }, 3),
+            0.3,
+        ),
+        experience::XpValue(10),
+        OnDeath(vec![DeathEffect::Explode {
+            radius: 1,
+            damage: 3,
+        }]),
+        Thinker::build()
+            .picker(FirstToScore { threshold: 0.5 })
+            .when(Asleep, Sleep)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .when(TargetVisible, ChaseTarget)
+            .when(RemembersPlayer, SearchForPlayer)
+            .when(HeardNoise, InvestigateNoise)
+            .otherwise(Wander),
+    ))
+    .id()
+}
+
+/// Rat is a neutral animal, it wanders around and fights no one. It is too small to block sight.
+pub(super) fn spawn_rat(cmd: &mut Commands, position: Position, texture: Handle<Image>) -> Entity {
+    cmd.spawn((
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            texture,
+            sprite: Sprite {
+                color: Color::rgb(0.6, 0.45, 0.3),
+                custom_size: Some(bevy::math::Vec2::splat(SPRITE_SIZE * 0.6)),
+                ..default()
+            },
+            ..default()
+        },
+        position,
+        Viewshed::new(4).with_mode(FovMode::Symmetric),
+        Monster,
+        Name("Rat".into()),
+        MonsterKind::Rat,
+        Faction::Animals,
+        CombatStats::new(4, 1, 0),
+        experience::XpValue(5),
+        Thinker::build()
+            .picker(FirstToScore { threshold: 0.5 })
+            .when(Asleep, Sleep)
+            .when(TargetInAttackRange, MeeleeAttackTarget)
+            .otherwise(Wander),
+    ))
+    .id()
 }
 
 pub(super) fn spawn_wall(cmd: &mut Commands, position: Position, texture: Handle<Image>) {
@@ -86,6 +390,31 @@ pub(super) fn spawn_wall(cmd: &mut Commands, position: Position, texture: Handle
     ));
 }
 
+/// Torch hanging on a wall, lighting up the room below it
+pub(super) fn spawn_torch(cmd: &mut Commands, position: Position) {
+    let color = Color::rgb(1., 0.6, 0.25);
+    cmd.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(bevy::math::Vec2::splat(SPRITE_SIZE / 4.)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                ITEM_Z,
+            )),
+            ..default()
+        },
+        position,
+        LightSource::new(6, color, 1.),
+        FogOfWar,
+        Name::new("Torch"),
+    ));
+}
+
 pub(super) fn spawn_floor(cmd: &mut Commands, position: Position, texture: Handle<Image>) {
     cmd.spawn((
         SpriteBundle {
@@ -104,23 +433,307 @@ pub(super) fn spawn_floor(cmd: &mut Commands, position: Position, texture: Handl
     ));
 }
 
-pub(super) fn spawn_health_potions(
+/// Spawns a potion of the magic `kind`, named by its appearance until the kind is identified. Health potion
+/// heals right away, regeneration potion heals a little and keeps healing for a while.
+pub(super) fn spawn_magic_potion(
     cmd: &mut Commands,
-    positions: Vec<Position>,
+    position: Position,
+    kind: MagicItemKind,
+    identities: &ItemIdentities,
     asset_server: &Res<AssetServer>,
-) {
-    positions
-        .into_iter()
-        .for_each(|position| spawn_potion(cmd, position, asset_server));
+) -> Entity {
+    let texture = asset_server.load("health_potion.png");
+    let mut potion = cmd.spawn((
+        SpriteBundle {
+            texture,
+            visibility: Visibility::Hidden,
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                FLOOR_Z,
+            )),
+            ..default()
+        },
+        position,
+        Item,
+        kind,
+        Name::new(identities.name(kind)),
+    ));
+    match kind {
+        MagicItemKind::RegenerationPotion => potion.insert((
+            Potion::new(2),
+            GrantsEffect(StatusEffect::new(
+                StatusEffectKind::Regeneration { amount: 1 },
+                10,
+            )),
+        )),
+        _ => potion.insert(Potion::new(8)),
+    };
+    potion.id()
 }
 
-pub(super) fn spawn_potion(
+pub(super) fn spawn_ration(
     cmd: &mut Commands,
     position: Position,
     asset_server: &Res<AssetServer>,
-) {
+) -> Entity {
+    let texture = asset_server.load("ration.png");
+    cmd.spawn((
+        SpriteBundle {
+            texture,
+            visibility: Visibility::Hidden,
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                FLOOR_Z,
+            )),
+            ..default()
+        },
+        position,
+        Item,
+        Food::new(hunger::Hunger::MAX_FOOD / 2),
+        Name::new("Ration"),
+    ))
+    .id()
+}
+
+pub(super) fn spawn_lamp_oil(
+    cmd: &mut Commands,
+    position: Position,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
     let texture = asset_server.load("health_potion.png");
     cmd.spawn((
+        SpriteBundle {
+            texture,
+            visibility: Visibility::Hidden,
+            sprite: Sprite {
+                color: Color::rgb(1., 0.75, 0.3),
+                ..default()
+            },
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                FLOOR_Z,
+            )),
+            ..default()
+        },
+        position,
+        Item,
+        LampOil::new(Lantern::MAX_FUEL / 2),
+        Name::new("Lamp Oil"),
+    ))
+    .id()
+}
+
+pub(super) fn spawn_scroll(
+    cmd: &mut Commands,
+    position: Position,
+    scroll: Scroll,
+    identities: &ItemIdentities,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    let texture = asset_server.load("scroll.png");
+    let kind = MagicItemKind::from(scroll);
+
+    cmd.spawn((
+        SpriteBundle {
+            texture,
+            visibility: Visibility::Hidden,
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                FLOOR_Z,
+            )),
+            ..default()
+        },
+        position,
+        Item,
+        scroll,
+        kind,
+        Name::new(identities.name(kind)),
+    ))
+    .id()
+}
+
+/// Spawns random item, potions being the most common
+pub(super) fn spawn_item(
+    cmd: &mut Commands,
+    position: Position,
+    identities: &ItemIdentities,
+    asset_server: &Res<AssetServer>,
+) {
+    let kind = match rand::thread_rng().gen_range(0f32..1f32) {
+        roll if roll > 0.93f32 => ItemKind::Scroll(Scroll::MagicMapping),
+        roll if roll > 0.9f32 => ItemKind::Scroll(Scroll::Telepathy {
+            turns: 30,
+            radius: 12,
+        }),
+        roll if roll > 0.88f32 => ItemKind::Scroll(Scroll::DetectItems),
+        roll if roll > 0.85f32 => ItemKind::Scroll(Scroll::RemoveCurse),
+        roll if roll > 0.7f32 => ItemKind::Scroll(Scroll::Confusion {
+            turns: 4,
+            range: 6,
+            radius: 2,
+        }),
+        roll if roll > 0.5f32 => ItemKind::Scroll(Scroll::Fireball {
+            damage: 8,
+            range: 6,
+            radius: 2,
+        }),
+        roll if roll > 0.35f32 => {
+            let equipment = [
+                EquipmentKind::Dagger,
+                EquipmentKind::Longsword,
+                EquipmentKind::Shield,
+                EquipmentKind::LeatherArmor,
+                EquipmentKind::Helmet,
+                EquipmentKind::RingOfStrength,
+                EquipmentKind::RingOfProtection,
+            ];
+            ItemKind::Equipment {
+                kind: equipment[rand::thread_rng().gen_range(0..equipment.len())],
+                cursed: rand::thread_rng().gen_bool(0.15),
+            }
+        }
+        roll if roll > 0.3f32 => ItemKind::Ration,
+        roll if roll > 0.25f32 => ItemKind::RegenerationPotion,
+        roll if roll > 0.18f32 => ItemKind::LampOil,
+        _ => ItemKind::HealthPotion,
+    };
+
+    spawn_item_of_kind(cmd, kind, position, identities, asset_server);
+}
+
+pub(super) fn spawn_item_of_kind(
+    cmd: &mut Commands,
+    kind: ItemKind,
+    position: Position,
+    identities: &ItemIdentities,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    let item = match kind {
+        ItemKind::HealthPotion => spawn_magic_potion(
+            cmd,
+            position,
+            MagicItemKind::HealthPotion,
+            identities,
+            asset_server,
+        ),
+        ItemKind::RegenerationPotion => spawn_magic_potion(
+            cmd,
+            position,
+            MagicItemKind::RegenerationPotion,
+            identities,
+            asset_server,
+        ),
+        ItemKind::Ration => spawn_ration(cmd, position, asset_server),
+        ItemKind::LampOil => spawn_lamp_oil(cmd, position, asset_server),
+        ItemKind::Scroll(scroll) => spawn_scroll(cmd, position, scroll, identities, asset_server),
+        ItemKind::Equipment { kind, cursed } => {
+            spawn_equipment(cmd, position, kind, cursed, asset_server)
+        }
+    };
+    cmd.entity(item).insert(Price(kind.price()));
+    item
+}
+
+pub(super) fn spawn_gold(cmd: &mut Commands, position: Position, asset_server: &Res<AssetServer>) {
+    let amount = rand::thread_rng().gen_range(5..=25);
+    cmd.spawn((
+        SpriteBundle {
+            texture: asset_server.load("gold.png"),
+            visibility: Visibility::Hidden,
+            transform: Transform::from_translation(Vec3::new(
+                position.x as f32 * SPRITE_SIZE,
+                position.y as f32 * SPRITE_SIZE,
+                FLOOR_Z,
+            )),
+            ..default()
+        },
+        position,
+        GoldPile(amount),
+        Name::new(format!("{amount} gold")),
+    ));
+}
+
+/// Spawns a vendor with a few random items in stock
+pub(super) fn spawn_vendor(
+    cmd: &mut Commands,
+    position: Position,
+    identities: &ItemIdentities,
+    asset_server: &Res<AssetServer>,
+) {
+    let vendor = cmd
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.load("hooded.png"),
+                sprite: Sprite {
+                    color: Color::GOLD,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                transform: Transform::from_translation(Vec3::new(
+                    position.x as f32 * SPRITE_SIZE,
+                    position.y as f32 * SPRITE_SIZE,
+                    MONSTER_Z,
+                )),
+                ..default()
+            },
+            position,
+            Vendor,
+            BlocksTile,
+            Name::new("Vendor"),
+        ))
+        .id();
+
+    let stock = [
+        ItemKind::HealthPotion,
+        ItemKind::HealthPotion,
+        ItemKind::RegenerationPotion,
+        ItemKind::Ration,
+        ItemKind::Ration,
+        ItemKind::LampOil,
+        ItemKind::Equipment {
+            kind: EquipmentKind::Longsword,
+            cursed: false,
+        },
+        ItemKind::Equipment {
+            kind: EquipmentKind::LeatherArmor,
+            cursed: false,
+        },
+        ItemKind::Equipment {
+            kind: EquipmentKind::Shield,
+            cursed: false,
+        },
+        ItemKind::Scroll(Scroll::MagicMapping),
+        ItemKind::Scroll(Scroll::RemoveCurse),
+    ];
+    for kind in stock {
+        let item = spawn_item_of_kind(cmd, kind, position, identities, asset_server);
+        cmd.entity(item)
+            .remove::<Position>()
+            .insert(InBackpack::new(vendor));
+    }
+}
+
+pub(super) fn spawn_equipment(
+    cmd: &mut Commands,
+    position: Position,
+    kind: EquipmentKind,
+    cursed: bool,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    let texture = asset_server.load(match kind.slot() {
+        EquipmentSlot::MeleeWeapon => "sword.png",
+        EquipmentSlot::Shield => "shield.png",
+        EquipmentSlot::Armor => "armor.png",
+        EquipmentSlot::Helmet => "helmet.png",
+        EquipmentSlot::Ring => "ring.png",
+    });
+    let (power, defense) = kind.bonuses(cursed);
+
+    let mut item = cmd.spawn((
         SpriteBundle {
             texture,
             visibility: Visibility::Hidden,
@@ -133,9 +746,15 @@ pub(super) fn spawn_potion(
         },
         position,
         Item,
-        Potion::new(8),
-        Name::new("Health Potion"),
+        Equippable::new(kind.slot()),
+        PowerBonus(power),
+        DefenseBonus(defense),
+        Name::new(kind.name()),
     ));
+    if cursed {
+        item.insert(Cursed::new());
+    }
+    item.id()
 }
 
 pub(super) fn populate_room(
@@ -143,12 +762,14 @@ pub(super) fn populate_room(
     room: &Rect,
     max_monsters: u8,
     max_items: u8,
+    identities: &ItemIdentities,
     asset_server: &Res<AssetServer>,
 ) {
     #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
     enum Spawn {
         Monster(Position),
         Item(Position),
+        Gold(Position),
     }
 
     let mut rng = rand::thread_rng();
@@ -187,10 +808,18 @@ pub(super) fn populate_room(
         }
     }
 
+    // at most one pile of gold per room
+    if rng.gen_bool(0.5) {
+        let (x, y) = room.rand_position();
+        let spawn_point = Spawn::Gold(Position::new(x, y, ITEM_Z as i32));
+        spawn_points.insert(spawn_point);
+    }
+
     spawn_points
         .into_iter()
         .for_each(|to_spawn| match to_spawn {
-            Spawn::Item(position) => spawn_potion(cmd, position, asset_server),
+            Spawn::Item(position) => spawn_item(cmd, position, identities, asset_server),
+            Spawn::Gold(position) => spawn_gold(cmd, position, asset_server),
             Spawn::Monster(position) => spawn_monster(cmd, position, asset_server),
         });
 }
@@ -209,8 +838,16 @@ pub(super) fn spawn_player(
         },
         position,
         crate::components::Player,
-        Viewshed::new(10),
+        Faction::Player,
+        Viewshed::new(10).with_mode(FovMode::Symmetric),
+        Lantern::new(),
         Name::new("Player"),
         CombatStats::new(30, 5, 2),
+        combat::RangedAttack::new(6),
+        experience::Experience::new(),
+        hunger::Hunger::new(),
+        shop::Wallet::new(0),
+        Stealth(2),
+        combat::NaturalRegeneration::new(),
     ));
 }
@@ -5,10 +5,17 @@ use crate::{
 use bevy::{app::Startup, prelude::*};
 pub use player::PlayerInitSet;
 
+mod bark;
 mod combat;
+mod hunger;
+mod item;
+mod light;
 mod map;
 mod monster;
 mod player;
+mod status;
+mod targeting;
+mod vision;
 
 pub struct InitSetup;
 
@@ -24,8 +31,14 @@ impl Plugin for InitSetup {
         app.insert_state(GameState::default())
             .add_plugins((
                 player::PlayerPlugin,
+                bark::BarkPlugin,
                 monster::MonsterPlugin,
                 combat::CombatSystemPlugin,
+                hunger::HungerPlugin,
+                item::ItemPlugin,
+                light::LightPlugin,
+                status::StatusEffectsPlugin,
+                targeting::TargetingPlugin,
             ))
             .add_systems(
                 Startup,
@@ -33,7 +46,8 @@ impl Plugin for InitSetup {
                     .in_set(InitSetupSet)
                     .run_if(run_once()),
             )
-            .add_systems(Update, check_player_death);
+            .add_event::<map::SpawnRequest>()
+            .add_systems(Update, (check_player_death, map::process_spawn_requests));
     }
 }
 
@@ -1,65 +1,192 @@
+use super::{map::Map, vision::compute_fov};
 use crate::{
-    algorithms::fov::MyVisibility,
-    components::{Monster, Position, Viewshed, Wall},
+    algorithms::dijkstra::{dijkstra_map, flee_map},
+    components::{
+        monster::{HeardSound, PlannedPath, PlayerMemory, Sleeping, WanderGoal},
+        spell::Spellbook,
+        BlocksTile, Floor, Monster, Player, Position,
+    },
+    resources::DijkstraMaps,
     states::GameState,
 };
 use bevy::{log::trace, prelude::*, utils::HashSet};
 use big_brain::BigBrainSet;
 
+/// Something loud happened at `origin`. The noise carries `volume` tiles along walkable tiles. The `source` that
+/// made the noise does not hear it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct Noise {
+    pub source: Entity,
+    pub origin: Position,
+    pub volume: i32,
+}
+
 pub(super) struct MonsterPlugin;
 
 impl Plugin for MonsterPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(
-            Update,
-            (
-                compute_fov,
+        app.init_resource::<DijkstraMaps>()
+            .add_event::<Noise>()
+            // noises are made during both turns, so they are heard right away
+            .add_systems(Update, hear_noises)
+            .add_systems(
+                Update,
                 (
+                    compute_fov::<With<Monster>>,
                     (
-                        crate::ai::player_visible_scorer_system,
-                        crate::ai::player_in_meelee_range_scorer,
+                        update_dijkstra_maps,
+                        crate::ai::remember_player,
+                        crate::ai::select_targets,
+                        crate::ai::alert_pack,
+                        forget_planned_paths,
+                        (
+                            crate::ai::target_visible_scorer_system,
+                            crate::ai::target_in_meelee_range_scorer,
+                            crate::ai::target_in_shooting_range_scorer,
+                            crate::ai::low_health_scorer,
+                            crate::ai::asleep_scorer,
+                            crate::ai::remembers_player_scorer,
+                            crate::ai::can_cast_scorer,
+                            crate::ai::heard_noise_scorer,
+                        )
+                            .in_set(BigBrainSet::Scorers),
+                        (
+                            crate::ai::chase_target,
+                            crate::ai::meelee_attack_target_action,
+                            crate::ai::shoot_target_action,
+                            crate::ai::flee_from_player,
+                            crate::ai::sleep_action,
+                            crate::ai::wander_action,
+                            crate::ai::search_for_player,
+                            crate::ai::cast_spell_action,
+                            crate::ai::investigate_noise,
+                        )
+                            .in_set(BigBrainSet::Actions),
+                        end_turn,
                     )
-                        .in_set(BigBrainSet::Scorers),
-                    (
-                        crate::ai::chase_player,
-                        crate::ai::meelee_attack_player_action,
-                    )
-                        .in_set(BigBrainSet::Actions),
-                    end_turn,
-                )
-                    .run_if(in_state(GameState::EnemyTurn))
-                    .chain(),
-            ),
-        );
+                        .run_if(in_state(GameState::EnemyTurn))
+                        .chain(),
+                ),
+            )
+            .add_systems(OnEnter(GameState::EnemyTurn), tick_spell_cooldowns);
     }
 }
 
-fn compute_fov(
-    mut monsters: Query<(&Position, &mut Viewshed), With<Monster>>,
-    walls: Query<&Position, With<Wall>>,
+/// How far from the player the approach map reaches, monsters further away do not chase the player anyway
+const APPROACH_MAP_RANGE: i32 = 40;
+
+/// How far from the player the flee map reaches
+const FLEE_MAP_RANGE: i32 = 15;
+
+/// Places monsters are heading to, other than the player
+type MonsterGoals<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static WanderGoal>,
+        Option<&'static PlayerMemory>,
+        Option<&'static HeardSound>,
+    ),
+    With<Monster>,
+>;
+
+/// Recomputes maps all the monsters use to approach the player or to flee from it, and the maps leading to the
+/// goals monsters already head to. Goals picked later during the turn get their maps on the first request.
+fn update_dijkstra_maps(
+    mut maps: ResMut<DijkstraMaps>,
+    map: Res<Map>,
+    blockers: Query<&Position, (With<BlocksTile>, Without<Monster>)>,
+    floors: Query<&Position, With<Floor>>,
+    ppos: Query<&Position, With<Player>>,
+    goals: MonsterGoals,
 ) {
-    fn compute_and_update_fov<'a>(
-        walls: &'a HashSet<&'a Position>,
-    ) -> impl FnMut((&'a Position, Mut<'a, Viewshed>)) -> () + 'a {
-        return move |(position, mut viewshed): (&'a Position, Mut<'a, Viewshed>)| {
-            let visible_tiles = MyVisibility::new(
-                |x, y| walls.iter().any(|pos| pos.x == x && pos.y == y),
-                |x, y| euclidean_distance(0, 0, x, y),
-            )
-            .compute(*position, viewshed.visible_range() as i32);
-            viewshed.set_visible_tiles(visible_tiles);
-        };
+    let ppos = *ppos.single();
+    let blockers = blockers.iter().collect::<HashSet<&Position>>();
+    let blocks = |pos: &Position| !map.is_floor(pos) || blockers.contains(pos);
+
+    maps.approach_player = dijkstra_map([ppos], APPROACH_MAP_RANGE, blocks);
+    maps.flee_player = flee_map([ppos], FLEE_MAP_RANGE, blocks);
+
+    maps.reset_goals(
+        floors
+            .iter()
+            .filter(|pos| !blockers.contains(pos))
+            .copied()
+            .collect(),
+    );
+    for (wander_goal, memory, heard_sound) in goals.iter() {
+        let goals = [
+            wander_goal.map(|WanderGoal(goal)| *goal),
+            memory.map(|memory| memory.last_seen),
+            heard_sound.map(|HeardSound(origin)| *origin),
+        ];
+        for goal in goals.into_iter().flatten() {
+            maps.goal(goal);
+        }
+    }
+}
+
+/// Sleeping monsters hearing a noise at least this loud wake up
+const WAKE_LOUDNESS: i32 = 4;
+
+/// Lets monsters hear [Noise]s. Noise spreads along walkable tiles and gets quieter with every step. Monsters within
+/// its reach go to investigate it, sleeping ones wake up if it is loud enough.
+fn hear_noises(
+    mut cmd: Commands,
+    mut noises: EventReader<Noise>,
+    map: Res<Map>,
+    blockers: Query<&Position, (With<BlocksTile>, Without<Monster>)>,
+    monsters: Query<(Entity, &Position, Has<Sleeping>), With<Monster>>,
+) {
+    if noises.is_empty() {
+        return;
     }
+    let blockers = blockers.iter().collect::<HashSet<&Position>>();
+    let blocks = |pos: &Position| !map.is_floor(pos) || blockers.contains(pos);
+
+    for Noise {
+        source,
+        origin,
+        volume,
+    } in noises.read()
+    {
+        let reach = dijkstra_map([*origin], *volume, blocks);
+
+        for (monster, pos, sleeping) in monsters.iter().filter(|(monster, ..)| monster != source) {
+            let Some(distance) = reach.get(pos) else {
+                continue;
+            };
+            let loudness = volume - distance;
+            trace!(?monster, ?origin, %loudness, "heard noise");
 
-    let walls = HashSet::from_iter(walls.into_iter());
+            match sleeping {
+                true if loudness >= WAKE_LOUDNESS => {
+                    cmd.entity(monster)
+                        .remove::<Sleeping>()
+                        .insert(HeardSound(*origin));
+                }
+                true => (),
+                false => {
+                    cmd.entity(monster).insert(HeardSound(*origin));
+                }
+            }
+        }
+    }
+}
 
-    monsters.iter_mut().for_each(compute_and_update_fov(&walls))
+/// Paths planned during the last turn are stale by now
+fn forget_planned_paths(mut cmd: Commands, planners: Query<Entity, With<PlannedPath>>) {
+    planners.iter().for_each(|planner| {
+        cmd.entity(planner).remove::<PlannedPath>();
+    });
 }
 
-fn euclidean_distance(p1_x: i32, p1_y: i32, p2_x: i32, p2_y: i32) -> i32 {
-    let dx = (p1_x - p2_x) as f64;
-    let dy = (p1_y - p2_y) as f64;
-    ((dx * dx + dy * dy).sqrt()) as i32
+/// Spells cool down once per round. Cooldowns tick at the start of the enemy turn, before anyone casts, so a spell
+/// cast this turn stays unavailable for its full cooldown.
+fn tick_spell_cooldowns(mut spellbooks: Query<&mut Spellbook>) {
+    spellbooks
+        .iter_mut()
+        .for_each(|mut spellbook| spellbook.tick());
 }
 
 fn end_turn(mut state: ResMut<NextState<GameState>>) {
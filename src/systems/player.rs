@@ -1,10 +1,20 @@
+use super::{
+    light::{apply_lighting, compute_light_map, remember_base_colors},
+    monster::Noise,
+    vision::compute_fov,
+};
+use crate::components::combat::{Health, NaturalRegeneration, RangedAttack};
+use crate::components::experience::Experience;
+use crate::components::hunger::{Hunger, HungerState};
 use crate::components::requests::MeeleeAttackRequest;
-use crate::components::BlocksSight;
+use crate::components::shop::Vendor;
+use crate::components::status::StatusEffects;
+use crate::resources::{LightMap, Shopping, Targeting, TargetingAction};
 use crate::states::GameState;
 use crate::{
     components::{
         self, requests::MovementRequest, BlocksTile, FogOfWar, Monster, Name, Player, Position,
-        Revealed, Viewshed, Visible,
+        Resting, Revealed, Stealth, Viewshed, Visible,
     },
     consts::FOW_ALPHA,
 };
@@ -27,20 +37,37 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_systems(
             Startup,
-            (compute_fov, update_visibility)
+            (
+                super::vision::compute_fov::<With<Player>>,
+                compute_light_map,
+                update_visibility,
+            )
                 .chain()
                 .run_if(run_once())
                 .after(super::InitSetupSet)
                 .in_set(PlayerInitSet),
         )
+        // regeneration happens once per round, same as status effects
+        .add_systems(OnExit(GameState::EnemyTurn), regenerate_health)
         .add_systems(
             Update,
             (
-                (player_input).run_if(in_state(GameState::PlayerTurn)),
+                (continue_resting, player_input)
+                    .chain()
+                    .run_if(in_state(GameState::PlayerTurn)),
                 super::process_movement,
+                make_footsteps,
                 super::sync_position,
                 sync_camera_with_player,
-                (compute_fov, update_visibility, apply_fow).chain(),
+                (
+                    compute_fov::<With<Player>>,
+                    compute_light_map,
+                    update_visibility,
+                    apply_fow,
+                    remember_base_colors,
+                    apply_lighting,
+                )
+                    .chain(),
                 // print_player_pos,
             )
                 .chain(),
@@ -48,16 +75,114 @@ impl Plugin for PlayerPlugin {
     }
 }
 
+/// Whether any monster stands inside the `viewshed`
+fn monster_in_sight<'a>(
+    viewshed: &Viewshed,
+    mut monsters: impl Iterator<Item = &'a Position>,
+) -> bool {
+    monsters.any(|pos| viewshed.contains(pos))
+}
+
+/// Keeps skipping turns of resting player. Resting ends when player is fully healed, a monster comes into view
+/// or any key is pressed.
+fn continue_resting(
+    mut cmd: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    input: Res<ButtonInput<KeyCode>>,
+    player: Query<(Entity, &Health, &Viewshed, Has<Resting>), With<Player>>,
+    monsters: Query<&Position, With<Monster>>,
+) {
+    let Ok((player_ent, health, viewshed, true)) = player.get_single() else {
+        return;
+    };
+
+    if input.get_just_pressed().next().is_some()
+        || health.current >= health.max
+        || monster_in_sight(viewshed, monsters.iter())
+    {
+        debug!("player stops resting");
+        cmd.entity(player_ent).remove::<Resting>();
+        return;
+    }
+
+    next_state.set(GameState::EnemyTurn);
+}
+
+/// Player recovers health when no monster is in sight. Starving player does not regenerate at all.
+fn regenerate_health(
+    mut player: Query<(&mut Health, &mut NaturalRegeneration), With<Player>>,
+    player_state: Query<(&Viewshed, &Experience, Option<&Hunger>), With<Player>>,
+    monsters: Query<&Position, With<Monster>>,
+) {
+    let (Ok((mut health, mut regeneration)), Ok((viewshed, experience, hunger))) =
+        (player.get_single_mut(), player_state.get_single())
+    else {
+        return;
+    };
+
+    let starving = hunger.is_some_and(|hunger| hunger.state() == HungerState::Starving);
+    if starving || monster_in_sight(viewshed, monsters.iter()) {
+        regeneration.interrupt();
+        return;
+    }
+
+    if regeneration.tick(experience.level) {
+        trace!("player regenerates");
+        health.heal(1);
+    }
+}
+
+/// How far the footsteps of a player without any [Stealth] carry
+const FOOTSTEPS_VOLUME: i32 = 4;
+
+/// Player together with how loudly it walks
+type FootstepMaker<'w, 's> =
+    Query<'w, 's, (Entity, Ref<'static, Position>, Option<&'static Stealth>), With<Player>>;
+
+/// Walking player makes a bit of [Noise], the stealthier the player, the quieter the steps
+fn make_footsteps(mut noises: EventWriter<Noise>, player: FootstepMaker) {
+    let Ok((source, origin, stealth)) = player.get_single() else {
+        return;
+    };
+    if !origin.is_changed() {
+        return;
+    }
+    let volume = FOOTSTEPS_VOLUME - stealth.map(|Stealth(stealth)| *stealth).unwrap_or_default();
+    if volume > 0 {
+        noises.send(Noise {
+            source,
+            origin: *origin,
+            volume,
+        });
+    }
+}
+
+/// Entities player cannot walk through, other than monsters which are attacked instead
+type Obstacles<'w, 's> =
+    Query<'w, 's, (Entity, &'static Position, Has<Vendor>), (With<BlocksTile>, Without<Monster>)>;
+
 /// This system handles user's input controlling player
 pub fn player_input(
     mut cmd: Commands,
     mut next_state: ResMut<NextState<GameState>>,
-    mut player: Query<(Entity, &Position, &mut Sprite), With<Player>>,
+    mut player: Query<(Entity, &Position, &mut Sprite, &StatusEffects, &Experience), With<Player>>,
     input: ResMut<ButtonInput<KeyCode>>,
-    impassable: Query<&Position, (With<BlocksTile>, Without<Monster>)>,
+    impassable: Obstacles,
     monsters: Query<(Entity, &Position), With<Monster>>,
+    ranged_attack: Query<&RangedAttack, With<Player>>,
 ) {
-    let (player_ent, player_pos, mut sprite) = player.single_mut();
+    let (player_ent, player_pos, mut sprite, status_effects, experience) = player.single_mut();
+
+    if experience.can_level_up() {
+        next_state.set(GameState::LevelUp);
+        return;
+    }
+
+    if status_effects.is_stunned() {
+        debug!("player is stunned, skipping turn");
+        next_state.set(GameState::EnemyTurn);
+        return;
+    }
 
     let (mut x, mut y) = (0, 0);
 
@@ -115,17 +240,40 @@ pub fn player_input(
         sprite.flip_x = false;
     }
 
+    // entering targeting mode to shoot at something
+    if input.just_pressed(KeyCode::KeyF) {
+        if let Ok(RangedAttack { range }) = ranged_attack.get_single() {
+            cmd.insert_resource(Targeting::new(TargetingAction::RangedAttack, *range));
+            next_state.set(GameState::Targeting);
+        }
+        return;
+    }
+
     // skpping turn
     if input.just_pressed(KeyCode::KeyS) || input.just_pressed(KeyCode::Numpad5) {
         next_state.set(GameState::EnemyTurn);
         return;
     };
 
+    // resting, skipping turns until healed or interrupted
+    if input.just_pressed(KeyCode::KeyR) {
+        debug!("player starts resting");
+        cmd.entity(player_ent).insert(Resting);
+        next_state.set(GameState::EnemyTurn);
+        return;
+    }
+
     // no movement
     if x == 0 && y == 0 {
         return;
     }
 
+    // confused player stumbles in a random direction
+    if status_effects.is_confused() {
+        MovementRequest { x, y } = MovementRequest::random();
+        debug!(%x, %y, "player is confused, moving in random direction");
+    }
+
     if let Some((monster_ent, _)) = monsters
         .iter()
         .find(|(_, pos)| **pos == (*player_pos + MovementRequest { x, y }))
@@ -137,10 +285,21 @@ pub fn player_input(
         return;
     }
 
-    if impassable
+    if let Some((blocker, _, is_vendor)) = impassable
         .iter()
-        .any(|pos| *pos == *player_pos + MovementRequest { x, y })
+        .find(|(_, pos, _)| **pos == *player_pos + MovementRequest { x, y })
     {
+        // bumping into a vendor opens the shop
+        if is_vendor {
+            cmd.insert_resource(Shopping::new(blocker));
+            next_state.set(GameState::Shop);
+            return;
+        }
+
+        // stumbling into a wall while confused still costs the turn
+        if status_effects.is_confused() {
+            next_state.set(GameState::EnemyTurn);
+        }
         return;
     }
 
@@ -148,20 +307,13 @@ pub fn player_input(
     next_state.set(GameState::EnemyTurn);
 }
 
-/// Computes player's current field of vision
-fn compute_fov(
-    mut player_pos: Query<(&Position, &mut Viewshed), With<Player>>,
-    blocks_sight: Query<&Position, With<BlocksSight>>,
-) {
-    let (p_position, mut viewshed) = player_pos.single_mut();
-    viewshed.visible_tiles = crate::algorithms::fov::MyVisibility::new(
-        |x, y| blocks_sight.iter().any(|pos| pos.x == x && pos.y == y),
-        |x, y| euclidean_distance(0, 0, x, y),
-    )
-    .compute(*p_position, viewshed.visible_range as i32);
-}
+/// Monsters standing in the dark can only be seen from this close
+const DARK_SIGHT_RANGE: i32 = 2;
 
-/// Sets and removes [Visible] component from entities based on player's current [Viewshed]
+/// Sets and removes [Visible] component from entities based on player's current [Viewshed]. Monsters have to stand
+/// in the light to be seen, unless they are within [DARK_SIGHT_RANGE]. Telepathic player senses all the monsters
+/// within the radius of the effect, no matter whether it can see them. Once the effect wears off, those out of
+/// sight lose [Visible] again.
 fn update_visibility(
     mut cmd: Commands,
     visible: Query<(Entity, &Position), With<Visible>>,
@@ -169,11 +321,25 @@ fn update_visibility(
         (Entity, &mut Visibility, &Position, Option<&Revealed>),
         Without<Visible>,
     >,
-    player_visible_tiles: Query<&Viewshed, With<Player>>,
+    monsters: Query<(), With<Monster>>,
+    player_visible_tiles: Query<(&Viewshed, &Position, &StatusEffects), With<Player>>,
+    light_map: Res<LightMap>,
 ) {
-    let viewshed = player_visible_tiles.single();
+    let (viewshed, player_pos, effects) = player_visible_tiles.single();
+    let telepathy_radius = effects.telepathy_radius();
+    let sees = |entity: Entity, pos: &Position| {
+        let is_monster = monsters.contains(entity);
+        let sensed = telepathy_radius.is_some_and(|radius| player_pos.distance(*pos) <= radius);
+
+        (is_monster && sensed)
+            || viewshed.contains(pos)
+                && (!is_monster
+                    || light_map.is_lit(pos)
+                    || player_pos.distance(*pos) <= DARK_SIGHT_RANGE)
+    };
+
     visible.iter().for_each(|(entity, position)| {
-        if !viewshed.visible_tiles.contains(position) {
+        if !sees(entity, position) {
             cmd.entity(entity).remove::<Visible>();
         }
     });
@@ -181,10 +347,7 @@ fn update_visibility(
     visibility
         .iter_mut()
         .filter_map(|(entity, visibility, pos, revealed)| {
-            viewshed
-                .visible_tiles
-                .contains(pos)
-                .then(|| (entity, visibility, revealed))
+            sees(entity, pos).then(|| (entity, visibility, revealed))
         })
         .for_each(|(entity, visibility, revealed)| {
             match revealed {
@@ -250,12 +413,6 @@ fn apply_fow(
     visited_sprites.iter_mut().for_each(set_opaque)
 }
 
-fn euclidean_distance(p1_x: i32, p1_y: i32, p2_x: i32, p2_y: i32) -> i32 {
-    let dx = (p1_x - p2_x) as f64;
-    let dy = (p1_y - p2_y) as f64;
-    ((dx * dx + dy * dy).sqrt()) as i32
-}
-
 /// We want camera to follow player, making the player's sprite always to be in the center.
 /// This function sets camera's [Transform] to player's [Transform]
 fn sync_camera_with_player(
@@ -0,0 +1,52 @@
+//! Status effects related systems
+//!
+//!
+
+use crate::{
+    components::{
+        combat::{Health, SufferDamage},
+        status::{StatusEffectKind, StatusEffects},
+        Name,
+    },
+    states::GameState,
+};
+use bevy::prelude::*;
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        // once monsters are done with their turn, the whole round is over
+        app.add_systems(OnExit(GameState::EnemyTurn), tick_status_effects);
+    }
+}
+
+/// Applies per-turn effects (damage and healing) and counts down remaining turns of all active effects
+fn tick_status_effects(
+    mut query: Query<(
+        Option<&Name>,
+        &mut StatusEffects,
+        &mut SufferDamage,
+        &mut Health,
+    )>,
+) {
+    for (name, mut effects, mut suffer_damage, mut health) in query.iter_mut() {
+        for effect in effects.iter() {
+            match effect.kind {
+                StatusEffectKind::Poison { damage } => {
+                    trace!(?name, %damage, "poison damage");
+                    suffer_damage.add_damage(damage, None);
+                }
+                StatusEffectKind::Regeneration { amount } => {
+                    trace!(?name, %amount, "regeneration");
+                    health.heal(amount);
+                }
+                StatusEffectKind::Confusion
+                | StatusEffectKind::Stun
+                | StatusEffectKind::Telepathy { .. } => (),
+            }
+        }
+
+        effects.tick();
+    }
+}
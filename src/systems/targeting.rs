@@ -0,0 +1,211 @@
+//! Targeting mode, in which player picks a target for a ranged action or an area affected by an item.
+//!
+//! Target can be picked either by cycling through visible monsters with `Tab` and confirming with `Enter` or `F`,
+//! or by clicking on a tile with the mouse. `Escape` cancels the targeting.
+
+use crate::{
+    algorithms::{fov::MyVisibility, line::has_line_of_fire},
+    components::{
+        requests::{RangedAttackRequest, UseItemRequest},
+        BlocksSight, Monster, Player, Position, Viewshed, Visible,
+    },
+    consts::{HIGHLIGHT_Z, SPRITE_SIZE},
+    resources::{CursorPosition, Targeting, TargetingAction},
+    states::GameState,
+};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Monsters the player can see, only those can be targeted
+type VisibleMonsters<'w, 's> =
+    Query<'w, 's, (Entity, &'static Position), (With<Monster>, With<Visible>)>;
+
+/// Marks sprites used to highlight the currently selected target
+#[derive(Debug, Clone, Copy, Component)]
+pub struct TargetHighlight;
+
+pub(super) struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Targeting), select_nearest_target)
+            .add_systems(
+                Update,
+                (targeting_input, update_target_highlight)
+                    .chain()
+                    .run_if(in_state(GameState::Targeting)),
+            )
+            .add_systems(OnExit(GameState::Targeting), clean_up_targeting);
+    }
+}
+
+/// Tiles affected by a blast of given `radius` centered at `center`. Blast does not pass through anything that
+/// `blocks` it, so it is computed the same way as field of view.
+pub(super) fn blast_area(
+    center: Position,
+    radius: i32,
+    blocks: impl Fn(&Position) -> bool,
+) -> HashSet<Position> {
+    MyVisibility::new(
+        |x, y| blocks(&Position::new(x, y, 0)),
+        |x, y| ((x * x + y * y) as f64).sqrt() as i32,
+    )
+    .compute(center, radius)
+}
+
+/// Positions of monsters player can see and which are within `range`, sorted from the closest one. The `monsters`
+/// are expected to be [Visible] already, the viewshed check leaves out those only sensed by telepathy.
+fn targetable_positions<'a>(
+    player_pos: &Position,
+    viewshed: &Viewshed,
+    range: i32,
+    monsters: impl Iterator<Item = &'a Position>,
+) -> Vec<Position> {
+    let mut positions = monsters
+        .filter(|pos| viewshed.contains(pos) && player_pos.distance(**pos) <= range)
+        .copied()
+        .collect::<Vec<Position>>();
+    positions.sort_by_key(|pos| player_pos.distance(*pos));
+    positions
+}
+
+fn select_nearest_target(
+    mut targeting: ResMut<Targeting>,
+    player: Query<(&Position, &Viewshed), With<Player>>,
+    monsters: Query<&Position, (With<Monster>, With<Visible>)>,
+) {
+    let (player_pos, viewshed) = player.single();
+    targeting.target = targetable_positions(player_pos, viewshed, targeting.range, monsters.iter())
+        .first()
+        .copied();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn targeting_input(
+    mut cmd: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut targeting: ResMut<Targeting>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    cursor_position: Res<CursorPosition>,
+    player: Query<(Entity, &Position, &Viewshed), With<Player>>,
+    monsters: VisibleMonsters,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+) {
+    let (player_ent, player_pos, viewshed) = player.single();
+
+    if keys.just_pressed(KeyCode::Escape) {
+        debug!("targeting cancelled");
+        next_state.set(GameState::PlayerTurn);
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Tab) {
+        let candidates = targetable_positions(
+            player_pos,
+            viewshed,
+            targeting.range,
+            monsters.iter().map(|(_, pos)| pos),
+        );
+        let next = targeting
+            .target
+            .and_then(|target| candidates.iter().position(|pos| *pos == target))
+            .map(|index| (index + 1) % candidates.len())
+            .unwrap_or_default();
+        targeting.target = candidates.get(next).copied();
+        return;
+    }
+
+    let confirmed = if mouse.just_pressed(MouseButton::Left) {
+        targeting.target = Some(*cursor_position.world_position());
+        true
+    } else {
+        keys.just_pressed(KeyCode::Enter) || keys.just_pressed(KeyCode::KeyF)
+    };
+
+    let (true, Some(target)) = (confirmed, targeting.target) else {
+        return;
+    };
+
+    if !viewshed.contains(&target) || player_pos.distance(target) > targeting.range {
+        debug!(?target, "target is not visible or out of range");
+        return;
+    }
+
+    match targeting.action {
+        TargetingAction::RangedAttack => {
+            let Some((monster, _)) = monsters.iter().find(|(_, pos)| **pos == target) else {
+                debug!(?target, "there is nothing to shoot at");
+                return;
+            };
+
+            let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+            if !has_line_of_fire(*player_pos, target, |pos| blockers.contains(pos)) {
+                debug!(?target, "no clear line of fire");
+                return;
+            }
+
+            cmd.entity(player_ent)
+                .insert(RangedAttackRequest::new(monster, targeting.range));
+        }
+        TargetingAction::UseItem(item) => {
+            cmd.entity(player_ent)
+                .insert(UseItemRequest::new(item, Some(target)));
+        }
+    }
+
+    next_state.set(GameState::EnemyTurn);
+}
+
+/// Highlights selected tile and, if the action affects an area, all the tiles that will be affected
+fn update_target_highlight(
+    mut cmd: Commands,
+    targeting: Res<Targeting>,
+    highlights: Query<Entity, With<TargetHighlight>>,
+    blast_blockers: Query<&Position, (With<BlocksSight>, Without<Monster>)>,
+) {
+    if !targeting.is_changed() {
+        return;
+    }
+
+    highlights
+        .iter()
+        .for_each(|entity| cmd.entity(entity).despawn());
+
+    let Some(target) = targeting.target else {
+        return;
+    };
+
+    let mut spawn_highlight = |position: Position, color: Color| {
+        cmd.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(SPRITE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    Position::new(position.x, position.y, HIGHLIGHT_Z as i32).into(),
+                ),
+                ..default()
+            },
+            TargetHighlight,
+        ));
+    };
+
+    if let Some(radius) = targeting.radius {
+        let blockers = blast_blockers.iter().collect::<HashSet<&Position>>();
+        blast_area(target, radius, |pos| blockers.contains(pos))
+            .into_iter()
+            .filter(|pos| *pos != target)
+            .for_each(|pos| spawn_highlight(pos, Color::rgba(1., 0.5, 0., 0.25)));
+    }
+
+    spawn_highlight(target, Color::rgba(1., 0., 0., 0.35));
+}
+
+fn clean_up_targeting(mut cmd: Commands, highlights: Query<Entity, With<TargetHighlight>>) {
+    highlights
+        .iter()
+        .for_each(|entity| cmd.entity(entity).despawn());
+    cmd.remove_resource::<Targeting>();
+}
@@ -0,0 +1,33 @@
+//! Field of view shared by every [Viewshed], the player and the monsters see by the same rules.
+//! Anything marked with [BlocksSight] is opaque. Which entities get the marker is decided when they are spawned,
+//! walls and large creatures block sight, small creatures do not. Each viewer computes its field of view with its own
+//! [FovMode](crate::algorithms::fov::FovMode). Within the range of both, seeing is mutual only among viewers using the
+//! symmetric mode, which is why the player and every monster but the slimes use it. Slimes sense their surroundings
+//! crudely by raycasting.
+
+use crate::components::{BlocksSight, Position, Viewshed};
+use bevy::{ecs::query::QueryFilter, prelude::*, utils::HashSet};
+
+/// Recomputes [Viewshed]s of all the viewers matching the filter `F`
+pub(super) fn compute_fov<F: QueryFilter>(
+    mut viewers: Query<(&Position, &mut Viewshed), F>,
+    blocks_sight: Query<&Position, With<BlocksSight>>,
+) {
+    let blockers = blocks_sight.iter().collect::<HashSet<&Position>>();
+
+    for (position, mut viewshed) in viewers.iter_mut() {
+        let visible_tiles = viewshed.mode.compute(
+            *position,
+            viewshed.visible_range() as i32,
+            |x, y| blockers.contains(&Position::new(x, y, position.z)),
+            |x, y| euclidean_distance(0, 0, x, y),
+        );
+        viewshed.set_visible_tiles(visible_tiles);
+    }
+}
+
+pub(super) fn euclidean_distance(p1_x: i32, p1_y: i32, p2_x: i32, p2_y: i32) -> i32 {
+    let dx = (p1_x - p2_x) as f64;
+    let dy = (p1_y - p2_y) as f64;
+    ((dx * dx + dy * dy).sqrt()) as i32
+}
@@ -0,0 +1,220 @@
+//! AI debug overlay toggled with `F3`. For every monster the player can see it shows the values of its scorers, the
+//! action its thinker has picked, its field of view and the path it plans to walk. The overlay is redrawn once per
+//! turn and whenever it is toggled.
+
+use crate::{
+    ai::{
+        Asleep, CanCast, CastSpell, ChaseTarget, FleeFromPlayer, HeardNoise, InvestigateNoise,
+        LowHealth, MeeleeAttackTarget, RemembersPlayer, SearchForPlayer, ShootTarget, Sleep,
+        TargetInAttackRange, TargetInShootingRange, TargetVisible, Wander,
+    },
+    components::{monster::PlannedPath, Monster, Position, Viewshed, Visible},
+    consts::{FONT_SIZE, HIGHLIGHT_Z, SPRITE_SIZE},
+    states::GameState,
+};
+use bevy::{prelude::*, sprite::Anchor, utils::HashMap};
+use big_brain::prelude::{ActionState, Actor, Score};
+use std::fmt::Debug;
+
+/// Whether the AI debug overlay is shown
+#[derive(Debug, Clone, Copy, Default, Resource)]
+pub struct AiDebugOverlay {
+    pub enabled: bool,
+}
+
+/// What each monster's AI is thinking, collected from the scorer and action entities of its thinker
+#[derive(Debug, Default, Resource)]
+struct AiDebugInfo {
+    scores: HashMap<Entity, Vec<(String, f32)>>,
+    actions: HashMap<Entity, String>,
+}
+
+/// Marks everything drawn by the overlay
+#[derive(Debug, Clone, Copy, Component)]
+pub struct AiDebugMarker;
+
+/// Monsters the overlay is drawn for, together with what is drawn
+type DebuggedMonsters<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Position,
+        &'static Viewshed,
+        Option<&'static PlannedPath>,
+    ),
+    (With<Monster>, With<Visible>),
+>;
+
+pub(super) struct AiDebugPlugin;
+
+impl Plugin for AiDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiDebugOverlay>()
+            .init_resource::<AiDebugInfo>()
+            .add_systems(
+                Update,
+                (
+                    toggle_overlay,
+                    (
+                        clear_overlay,
+                        (
+                            (
+                                collect_scores::<TargetVisible>,
+                                collect_scores::<TargetInAttackRange>,
+                                collect_scores::<TargetInShootingRange>,
+                                collect_scores::<LowHealth>,
+                                collect_scores::<Asleep>,
+                                collect_scores::<RemembersPlayer>,
+                                collect_scores::<CanCast>,
+                                collect_scores::<HeardNoise>,
+                            ),
+                            (
+                                collect_actions::<ChaseTarget>,
+                                collect_actions::<MeeleeAttackTarget>,
+                                collect_actions::<ShootTarget>,
+                                collect_actions::<FleeFromPlayer>,
+                                collect_actions::<Sleep>,
+                                collect_actions::<Wander>,
+                                collect_actions::<SearchForPlayer>,
+                                collect_actions::<CastSpell>,
+                                collect_actions::<InvestigateNoise>,
+                            ),
+                            draw_overlay,
+                        )
+                            .chain()
+                            .run_if(overlay_enabled),
+                    )
+                        .chain()
+                        .run_if(
+                            resource_changed::<AiDebugOverlay>.or_else(state_changed::<GameState>),
+                        ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn overlay_enabled(overlay: Res<AiDebugOverlay>) -> bool {
+    overlay.enabled
+}
+
+fn toggle_overlay(input: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<AiDebugOverlay>) {
+    if input.just_pressed(KeyCode::F3) {
+        overlay.enabled = !overlay.enabled;
+        debug!(enabled = %overlay.enabled, "toggled AI debug overlay");
+    }
+}
+
+/// Overlay is drawn from scratch, once per turn is enough as monsters only think during their turn
+fn clear_overlay(
+    mut cmd: Commands,
+    mut info: ResMut<AiDebugInfo>,
+    markers: Query<Entity, With<AiDebugMarker>>,
+) {
+    markers
+        .iter()
+        .for_each(|entity| cmd.entity(entity).despawn());
+    info.scores.clear();
+    info.actions.clear();
+}
+
+/// Records the score of every scorer of type `T`
+fn collect_scores<T: Component + Debug>(
+    mut info: ResMut<AiDebugInfo>,
+    scorers: Query<(&Actor, &Score, &T)>,
+) {
+    for (Actor(actor), score, scorer) in scorers.iter() {
+        info.scores
+            .entry(*actor)
+            .or_default()
+            .push((format!("{scorer:?}"), score.get()));
+    }
+}
+
+/// Records which monsters have picked an action of type `T`
+fn collect_actions<T: Component + Debug>(
+    mut info: ResMut<AiDebugInfo>,
+    actions: Query<(&Actor, &ActionState, &T)>,
+) {
+    for (Actor(actor), state, action) in actions.iter() {
+        info.actions
+            .insert(*actor, format!("{action:?} ({state:?})"));
+    }
+}
+
+fn draw_overlay(mut cmd: Commands, info: Res<AiDebugInfo>, monsters: DebuggedMonsters) {
+    let mut spawn_highlight = |position: &Position, color: Color| {
+        cmd.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(SPRITE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    Position::new(position.x, position.y, HIGHLIGHT_Z as i32).into(),
+                ),
+                ..default()
+            },
+            AiDebugMarker,
+        ));
+    };
+
+    for (_, _, viewshed, path) in monsters.iter() {
+        viewshed
+            .visible_tiles
+            .iter()
+            .for_each(|pos| spawn_highlight(pos, Color::rgba(1., 1., 0., 0.08)));
+        path.iter()
+            .flat_map(|PlannedPath(path)| path.iter())
+            .for_each(|pos| spawn_highlight(pos, Color::rgba(0., 0.6, 1., 0.35)));
+    }
+
+    let style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+
+    for (monster, position, ..) in monsters.iter() {
+        let mut scores = info.scores.get(&monster).cloned().unwrap_or_default();
+        scores.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut sections = scores
+            .into_iter()
+            .map(|(label, score)| {
+                let color = match score > 0. {
+                    true => Color::GREEN,
+                    false => Color::GRAY,
+                };
+                TextSection::new(format!("{label}: {score:.2}\n"), style(color))
+            })
+            .collect::<Vec<_>>();
+        sections.push(TextSection::new(
+            format!(
+                "> {}",
+                info.actions
+                    .get(&monster)
+                    .map(String::as_str)
+                    .unwrap_or("nothing")
+            ),
+            style(Color::YELLOW),
+        ));
+
+        let mut transform =
+            Transform::from_translation(Position::new(position.x, position.y, 0).into());
+        transform.translation.y += SPRITE_SIZE / 2.;
+        transform.translation.z = HIGHLIGHT_Z + 1.;
+
+        cmd.spawn((
+            Text2dBundle {
+                text: Text::from_sections(sections),
+                text_anchor: Anchor::BottomCenter,
+                transform,
+                ..default()
+            },
+            AiDebugMarker,
+        ));
+    }
+}
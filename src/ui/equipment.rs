@@ -0,0 +1,106 @@
+use crate::{
+    components::{
+        combat::{Defense, Power},
+        equipment::{total_bonuses, DefenseBonus, EquipmentSlot, Equipped, PowerBonus},
+        shop::Wallet,
+        Name, Player,
+    },
+    consts::{DEFAULT_TEXT_COLOR, FONT_SIZE},
+};
+use bevy::prelude::*;
+
+/// Marks the text listing equipment worn by the player
+#[derive(Debug, Clone, Copy, Component)]
+pub struct EquipmentText;
+
+pub(super) struct EquipmentPanelPlugin;
+
+impl Plugin for EquipmentPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            spawn_equipment_panel
+                .run_if(run_once())
+                .after(crate::systems::PlayerInitSet),
+        )
+        .add_systems(Update, update_equipment_panel);
+    }
+}
+
+fn spawn_equipment_panel(mut cmd: Commands) {
+    cmd.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0f32),
+            right: Val::Px(0f32),
+            width: Val::Percent(18f32),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(10f32)),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::rgba(0.16, 0.16, 0.16, 0.75)),
+        ..default()
+    })
+    .with_children(|parent| {
+        parent.spawn((TextBundle::default(), EquipmentText));
+    });
+}
+
+fn update_equipment_panel(
+    mut text: Query<&mut Text, With<EquipmentText>>,
+    player: Query<(Entity, &Power, &Defense, &Wallet), With<Player>>,
+    equipment: Query<(&Name, &Equipped, Option<&PowerBonus>, Option<&DefenseBonus>)>,
+) {
+    let (Ok(mut text), Ok((player, power, defense, wallet))) =
+        (text.get_single_mut(), player.get_single())
+    else {
+        return;
+    };
+
+    let style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+
+    let (power_bonus, defense_bonus) = total_bonuses(
+        player,
+        equipment
+            .iter()
+            .map(|(_, equipped, power, defense)| (equipped, power, defense)),
+    );
+
+    let mut sections = vec![
+        TextSection::new("Equipment\n", style(Color::GOLD)),
+        TextSection::new(
+            format!(
+                "Power: {}  Defense: {}\n",
+                power.0 + power_bonus,
+                defense.0 + defense_bonus
+            ),
+            style(DEFAULT_TEXT_COLOR),
+        ),
+        TextSection::new(format!("Gold: {}\n", wallet.gold), style(Color::GOLD)),
+    ];
+
+    for slot in EquipmentSlot::ALL {
+        let mut worn = equipment
+            .iter()
+            .filter(|(_, equipped, ..)| equipped.owner == player && equipped.slot == slot)
+            .map(|(name, ..)| name.to_string())
+            .collect::<Vec<_>>();
+        worn.sort();
+        let worn = match worn.is_empty() {
+            true => "-".to_string(),
+            false => worn.join(", "),
+        };
+
+        sections.push(TextSection::new(
+            format!("{slot}: "),
+            style(DEFAULT_TEXT_COLOR),
+        ));
+        sections.push(TextSection::new(format!("{worn}\n"), style(Color::CYAN)));
+    }
+
+    text.sections = sections;
+}
@@ -0,0 +1,192 @@
+use crate::{
+    components::{
+        equipment::{Equippable, Equipped},
+        item::{Food, InBackpack, Potion, Scroll},
+        light::LampOil,
+        requests::UseItemRequest,
+        Name, Player,
+    },
+    consts::{DEFAULT_TEXT_COLOR, FONT_SIZE},
+    resources::{Targeting, TargetingAction},
+    states::GameState,
+};
+use bevy::prelude::*;
+
+/// Marks the root node of the inventory menu
+#[derive(Debug, Clone, Copy, Component)]
+pub struct InventoryMenu;
+
+pub(super) struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                open_inventory.run_if(in_state(GameState::PlayerTurn)),
+                inventory_input.run_if(in_state(GameState::Inventory)),
+            ),
+        )
+        .add_systems(OnEnter(GameState::Inventory), spawn_inventory_menu)
+        .add_systems(OnExit(GameState::Inventory), despawn_inventory_menu);
+    }
+}
+
+/// Keys used to select items, item at index `0` is selected by the first key and so on
+const ITEM_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+type CarriedItem<'a> = (
+    Entity,
+    &'a Name,
+    Option<&'a InBackpack>,
+    Option<&'a Equipped>,
+);
+
+/// What the item does when used: drunk, read, worn, eaten or poured into a lantern
+type UsableItem<'a> = (
+    Option<&'a Potion>,
+    Option<&'a Scroll>,
+    Has<Equippable>,
+    Has<Food>,
+    Has<LampOil>,
+);
+
+/// Items carried by the `owner`, backpack items first and worn equipment after them. Ordered so the listing
+/// and the selection always match. The returned flag tells whether the item is worn.
+fn backpack_items<'a>(
+    owner: Entity,
+    items: impl Iterator<Item = CarriedItem<'a>>,
+) -> Vec<(Entity, &'a Name, bool)> {
+    let mut backpack = items
+        .filter_map(
+            |(entity, name, in_backpack, equipped)| match (in_backpack, equipped) {
+                (Some(in_backpack), _) if in_backpack.owner == owner => Some((entity, name, false)),
+                (_, Some(equipped)) if equipped.owner == owner => Some((entity, name, true)),
+                _ => None,
+            },
+        )
+        .collect::<Vec<_>>();
+    backpack.sort_by_key(|(entity, _, equipped)| (*equipped, *entity));
+    backpack
+}
+
+fn open_inventory(input: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if input.just_pressed(KeyCode::KeyI) {
+        next_state.set(GameState::Inventory);
+    }
+}
+
+fn spawn_inventory_menu(
+    mut cmd: Commands,
+    player: Query<Entity, With<Player>>,
+    items: Query<CarriedItem>,
+) {
+    let backpack = backpack_items(player.single(), items.iter());
+
+    let text_style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+
+    cmd.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35f32),
+                top: Val::Percent(20f32),
+                width: Val::Percent(30f32),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10f32)),
+                row_gap: Val::Px(5f32),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.16, 0.16, 0.16, 0.9)),
+            ..default()
+        },
+        InventoryMenu,
+    ))
+    .with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "Backpack (Esc to close)",
+            text_style(Color::GOLD),
+        ));
+
+        if backpack.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                "Your backpack is empty.",
+                text_style(DEFAULT_TEXT_COLOR),
+            ));
+        }
+
+        backpack.iter().take(ITEM_KEYS.len()).enumerate().for_each(
+            |(index, (_, name, equipped))| {
+                let (label, color) = match equipped {
+                    true => (format!("[{}] {name} (equipped)", index + 1), Color::CYAN),
+                    false => (format!("[{}] {name}", index + 1), DEFAULT_TEXT_COLOR),
+                };
+                parent.spawn(TextBundle::from_section(label, text_style(color)));
+            },
+        );
+    });
+}
+
+fn inventory_input(
+    mut cmd: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    input: Res<ButtonInput<KeyCode>>,
+    player: Query<Entity, With<Player>>,
+    items: Query<CarriedItem>,
+    usable: Query<UsableItem>,
+) {
+    if input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::PlayerTurn);
+        return;
+    }
+
+    let Some(index) = ITEM_KEYS.iter().position(|key| input.just_pressed(*key)) else {
+        return;
+    };
+
+    let player = player.single();
+    let Some((item, name, _)) = backpack_items(player, items.iter()).get(index).copied() else {
+        return;
+    };
+
+    match usable.get(item) {
+        Ok((Some(_), ..)) | Ok((_, _, true, ..)) | Ok((_, _, _, true, _)) | Ok((.., true)) => {
+            cmd.entity(player).insert(UseItemRequest::new(item, None));
+            next_state.set(GameState::EnemyTurn);
+        }
+        Ok((_, Some(scroll), ..)) => match (scroll.range(), scroll.radius()) {
+            (Some(range), radius) => {
+                let targeting = Targeting::new(TargetingAction::UseItem(item), range);
+                cmd.insert_resource(match radius {
+                    Some(radius) => targeting.with_radius(radius),
+                    None => targeting,
+                });
+                next_state.set(GameState::Targeting);
+            }
+            (None, _) => {
+                cmd.entity(player).insert(UseItemRequest::new(item, None));
+                next_state.set(GameState::EnemyTurn);
+            }
+        },
+        _ => debug!(%name, "item cannot be used"),
+    }
+}
+
+fn despawn_inventory_menu(mut cmd: Commands, menu: Query<Entity, With<InventoryMenu>>) {
+    menu.iter()
+        .for_each(|entity| cmd.entity(entity).despawn_recursive());
+}
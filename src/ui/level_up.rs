@@ -0,0 +1,149 @@
+use crate::{
+    components::{
+        combat::{Defense, Health, Power},
+        experience::Experience,
+        Name, Player,
+    },
+    consts::{DEFAULT_TEXT_COLOR, FONT_SIZE},
+    states::GameState,
+    ui::log::LogMessage,
+};
+use bevy::prelude::*;
+
+/// Marks the root node of the level up menu
+#[derive(Debug, Clone, Copy, Component)]
+pub struct LevelUpMenu;
+
+pub(super) struct LevelUpPlugin;
+
+impl Plugin for LevelUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::LevelUp), spawn_level_up_menu)
+            .add_systems(Update, level_up_input.run_if(in_state(GameState::LevelUp)))
+            .add_systems(OnExit(GameState::LevelUp), despawn_level_up_menu);
+    }
+}
+
+/// Stats player can choose to improve when leveling up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LevelUpChoice {
+    Toughness,
+    Strength,
+    Agility,
+}
+
+impl LevelUpChoice {
+    const ALL: [LevelUpChoice; 3] = [
+        LevelUpChoice::Toughness,
+        LevelUpChoice::Strength,
+        LevelUpChoice::Agility,
+    ];
+
+    fn key(&self) -> KeyCode {
+        match self {
+            LevelUpChoice::Toughness => KeyCode::Digit1,
+            LevelUpChoice::Strength => KeyCode::Digit2,
+            LevelUpChoice::Agility => KeyCode::Digit3,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            LevelUpChoice::Toughness => "[1] Toughness: +10 max HP",
+            LevelUpChoice::Strength => "[2] Strength: +1 power",
+            LevelUpChoice::Agility => "[3] Agility: +1 defense",
+        }
+    }
+}
+
+fn spawn_level_up_menu(mut cmd: Commands, player: Query<&Experience, With<Player>>) {
+    let experience = player.single();
+
+    let text_style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+
+    cmd.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35f32),
+                top: Val::Percent(30f32),
+                width: Val::Percent(30f32),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10f32)),
+                row_gap: Val::Px(5f32),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.16, 0.16, 0.16, 0.9)),
+            ..default()
+        },
+        LevelUpMenu,
+    ))
+    .with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            format!(
+                "Level up! Choose your reward for level {}:",
+                experience.level + 1
+            ),
+            text_style(Color::GOLD),
+        ));
+
+        LevelUpChoice::ALL.iter().for_each(|choice| {
+            parent.spawn(TextBundle::from_section(
+                choice.description(),
+                text_style(DEFAULT_TEXT_COLOR),
+            ));
+        });
+    });
+}
+
+fn level_up_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut player: Query<
+        (
+            &Name,
+            &mut Experience,
+            &mut Health,
+            &mut Power,
+            &mut Defense,
+        ),
+        With<Player>,
+    >,
+) {
+    let Some(choice) = LevelUpChoice::ALL
+        .into_iter()
+        .find(|choice| input.just_pressed(choice.key()))
+    else {
+        return;
+    };
+
+    let (name, mut experience, mut health, mut power, mut defense) = player.single_mut();
+
+    match choice {
+        LevelUpChoice::Toughness => health.max += 10,
+        LevelUpChoice::Strength => power.0 += 1,
+        LevelUpChoice::Agility => defense.0 += 1,
+    }
+
+    // reaching new level fully restores health
+    health.current = health.max;
+    experience.level_up();
+    debug!(?choice, level = %experience.level, "player leveled up");
+
+    log_event_writer.send(LogMessage::LevelUp {
+        time: chrono::Local::now(),
+        name: name.clone(),
+        level: experience.level,
+    });
+    next_state.set(GameState::PlayerTurn);
+}
+
+fn despawn_level_up_menu(mut cmd: Commands, menu: Query<Entity, With<LevelUpMenu>>) {
+    menu.iter()
+        .for_each(|entity| cmd.entity(entity).despawn_recursive());
+}
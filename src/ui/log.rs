@@ -1,5 +1,12 @@
 use crate::{
-    components::{combat::Health, ui::*, Name, Player},
+    components::{
+        combat::Health,
+        hunger::{Hunger, HungerState},
+        spell::Spell,
+        status::{StatusEffectKind, StatusEffects},
+        ui::*,
+        Name, Player,
+    },
     consts::{DEFAULT_TEXT_COLOR, FONT_SIZE},
 };
 use bevy::prelude::*;
@@ -17,7 +24,15 @@ impl Plugin for LogUiPlugin {
                     .run_if(run_once())
                     .after(crate::systems::PlayerInitSet),
             )
-            .add_systems(Update, (update_log_texts, update_hp_bar));
+            .add_systems(
+                Update,
+                (
+                    update_log_texts,
+                    update_hp_bar,
+                    update_hunger_text,
+                    update_status_effects_text,
+                ),
+            );
     }
 }
 
@@ -39,6 +54,78 @@ pub enum LogMessage {
         time: chrono::DateTime<Local>,
         name: Name,
     },
+    /// Entity has advanced to the next level
+    LevelUp {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        /// The newly reached level
+        level: i32,
+    },
+    /// Entity has put an item into its backpack
+    ItemPickedUp {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity has used up an item
+    ItemUsed {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity has put on a piece of equipment
+    ItemEquipped {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity has taken off a piece of equipment
+    ItemUnequipped {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity has bought an item from a vendor
+    ItemBought {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity has sold an item to a vendor
+    ItemSold {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Entity cannot take off a cursed piece of equipment
+    ItemCursed {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        item: Name,
+    },
+    /// Monster shouting something
+    Bark {
+        time: chrono::DateTime<Local>,
+        name: Name,
+        line: String,
+    },
+    /// Entity casting a spell, possibly at some `target`
+    SpellCast {
+        time: chrono::DateTime<Local>,
+        caster: Name,
+        spell: Spell,
+        target: Option<Name>,
+    },
+    /// Lantern of the entity is running out of fuel
+    LanternFlickers {
+        time: chrono::DateTime<Local>,
+        name: Name,
+    },
+    /// Lantern of the entity ran out of fuel
+    LanternOut {
+        time: chrono::DateTime<Local>,
+        name: Name,
+    },
 }
 
 impl From<&LogMessage> for TextBundle {
@@ -141,6 +228,486 @@ impl From<&LogMessage> for TextBundle {
                     },
                 },
             ]),
+            LogMessage::LevelUp { time, name, level } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " has reached level ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{level}."),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::GOLD,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemPickedUp { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " picked up ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::CYAN,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemUsed { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " used ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::CYAN,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemEquipped { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " equipped ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::CYAN,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemUnequipped { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " took off ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::CYAN,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemBought { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " bought ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::GOLD,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemSold { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " sold ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::GOLD,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::ItemCursed { time, name, item } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " is bound by the curse of ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{item}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::PURPLE,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: ".".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::Bark { time, name, line } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " shouts: ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("\"{line}\""),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::ORANGE,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::SpellCast {
+                time,
+                caster,
+                spell,
+                target,
+            } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{caster}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: " casts ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{spell}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::VIOLET,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: match target {
+                        Some(target) => format!(" at {target}."),
+                        None => ".".to_string(),
+                    },
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::LanternFlickers { time, name } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: "'s lantern ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: "flickers, it is running out of oil.".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::ORANGE,
+                        ..default()
+                    },
+                },
+            ]),
+            LogMessage::LanternOut { time, name } => TextBundle::from_sections([
+                TextSection {
+                    value: format!("{}: ", time.format("%H:%M:%S%.3f")),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: format!("{name}"),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: "'s lantern ".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: DEFAULT_TEXT_COLOR,
+                        ..default()
+                    },
+                },
+                TextSection {
+                    value: "goes out.".to_string(),
+                    style: TextStyle {
+                        font_size: FONT_SIZE,
+                        color: Color::RED,
+                        ..default()
+                    },
+                },
+            ]),
         }
     }
 }
@@ -220,6 +787,34 @@ fn spawn_log_ui(mut cmd: bevy::prelude::Commands, player_hp: Query<&Health, With
                         HpText,
                     ));
                 });
+
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Percent(9.5f32),
+                        left: Val::Percent(46f32),
+                        padding: UiRect::all(Val::Px(10f32)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                HungerText,
+            ));
+
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Percent(9.5f32),
+                        left: Val::Percent(58f32),
+                        padding: UiRect::all(Val::Px(10f32)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                StatusEffectsText,
+            ));
         })
         .push_children(&[init_message]);
     cmd.spawn(Messages::new([Some(init_message), None, None, None, None]));
@@ -297,6 +892,59 @@ fn update_hp_bar(
     ];
 }
 
+fn update_hunger_text(
+    mut hunger_text: Query<&mut Text, With<HungerText>>,
+    player_hunger: Query<&Hunger, (With<Player>, Changed<Hunger>)>,
+) {
+    let Ok(hunger) = player_hunger.get_single() else {
+        return;
+    };
+
+    let state = hunger.state();
+    hunger_text.single_mut().sections = vec![TextSection::new(
+        format!("{state}"),
+        TextStyle {
+            font_size: FONT_SIZE,
+            color: match state {
+                HungerState::WellFed => Color::GREEN,
+                HungerState::Normal => Color::WHITE,
+                HungerState::Hungry => Color::ORANGE,
+                HungerState::Starving => Color::RED,
+            },
+            ..default()
+        },
+    )];
+}
+
+fn update_status_effects_text(
+    mut status_text: Query<&mut Text, With<StatusEffectsText>>,
+    player_effects: Query<&StatusEffects, (With<Player>, Changed<StatusEffects>)>,
+) {
+    let Ok(effects) = player_effects.get_single() else {
+        return;
+    };
+
+    status_text.single_mut().sections = effects
+        .iter()
+        .map(|effect| {
+            TextSection::new(
+                format!("{} ({}) ", effect.kind, effect.turns_left),
+                TextStyle {
+                    font_size: FONT_SIZE,
+                    color: match effect.kind {
+                        StatusEffectKind::Poison { .. } => Color::LIME_GREEN,
+                        StatusEffectKind::Confusion => Color::FUCHSIA,
+                        StatusEffectKind::Stun => Color::GOLD,
+                        StatusEffectKind::Regeneration { .. } => Color::CYAN,
+                        StatusEffectKind::Telepathy { .. } => Color::VIOLET,
+                    },
+                    ..default()
+                },
+            )
+        })
+        .collect();
+}
+
 #[inline]
 fn get_percentage(value: i32, percent: f32) -> i32 {
     (value as f32 * percent) as i32
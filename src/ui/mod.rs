@@ -1,4 +1,9 @@
+mod ai_debug;
+mod equipment;
+mod inventory;
+mod level_up;
 pub mod log;
+mod shop;
 mod tooltip;
 
 use bevy::prelude::*;
@@ -9,6 +14,14 @@ pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((log::LogUiPlugin, tooltip::TooltipPlugin));
+        app.add_plugins((
+            log::LogUiPlugin,
+            tooltip::TooltipPlugin,
+            level_up::LevelUpPlugin,
+            inventory::InventoryPlugin,
+            equipment::EquipmentPanelPlugin,
+            shop::ShopPlugin,
+            ai_debug::AiDebugPlugin,
+        ));
     }
 }
@@ -0,0 +1,212 @@
+//! Trading with a vendor. `Tab` switches between buying and selling, digits pick the item and `Escape` leaves.
+
+use crate::{
+    components::{
+        item::InBackpack,
+        shop::{Price, Wallet},
+        Name, Player,
+    },
+    consts::{DEFAULT_TEXT_COLOR, FONT_SIZE},
+    resources::{ShopMode, Shopping},
+    states::GameState,
+    ui::log::LogMessage,
+};
+use bevy::prelude::*;
+
+/// Marks the root node of the shop menu
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ShopMenu;
+
+pub(super) struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (shop_input, refresh_shop_menu)
+                .chain()
+                .run_if(in_state(GameState::Shop)),
+        )
+        .add_systems(OnExit(GameState::Shop), clean_up_shop);
+    }
+}
+
+/// Keys used to select items, item at index `0` is selected by the first key and so on
+const ITEM_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Priced items in the backpack of the `owner` together with the price they trade for in given `mode`
+fn wares<'a>(
+    owner: Entity,
+    mode: ShopMode,
+    items: impl Iterator<Item = (Entity, &'a Name, &'a InBackpack, &'a Price)>,
+) -> Vec<(Entity, &'a Name, i32)> {
+    let mut wares = items
+        .filter(|(_, _, in_backpack, _)| in_backpack.owner == owner)
+        .map(|(entity, name, _, price)| match mode {
+            ShopMode::Buy => (entity, name, price.0),
+            ShopMode::Sell => (entity, name, price.sell_value()),
+        })
+        .collect::<Vec<_>>();
+    wares.sort_by_key(|(entity, ..)| *entity);
+    wares
+}
+
+fn shop_input(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut log_event_writer: EventWriter<LogMessage>,
+    mut shopping: ResMut<Shopping>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut player: Query<(Entity, &Name, &mut Wallet), With<Player>>,
+    mut items: Query<(Entity, &Name, &mut InBackpack, &Price)>,
+) {
+    if input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::PlayerTurn);
+        return;
+    }
+
+    if input.just_pressed(KeyCode::Tab) {
+        shopping.mode = match shopping.mode {
+            ShopMode::Buy => ShopMode::Sell,
+            ShopMode::Sell => ShopMode::Buy,
+        };
+        return;
+    }
+
+    let Some(index) = ITEM_KEYS.iter().position(|key| input.just_pressed(*key)) else {
+        return;
+    };
+
+    let (player, player_name, mut wallet) = player.single_mut();
+    let (seller, buyer) = match shopping.mode {
+        ShopMode::Buy => (shopping.vendor, player),
+        ShopMode::Sell => (player, shopping.vendor),
+    };
+
+    let Some((item, item_name, price)) = wares(seller, shopping.mode, items.iter())
+        .get(index)
+        .map(|(item, name, price)| (*item, (*name).clone(), *price))
+    else {
+        return;
+    };
+
+    let message = match shopping.mode {
+        ShopMode::Buy => {
+            if !wallet.pay(price) {
+                debug!(%item_name, %price, "not enough gold");
+                return;
+            }
+            LogMessage::ItemBought {
+                time: chrono::Local::now(),
+                name: player_name.clone(),
+                item: item_name,
+            }
+        }
+        ShopMode::Sell => {
+            wallet.earn(price);
+            LogMessage::ItemSold {
+                time: chrono::Local::now(),
+                name: player_name.clone(),
+                item: item_name,
+            }
+        }
+    };
+
+    if let Ok((_, _, mut in_backpack, _)) = items.get_mut(item) {
+        in_backpack.owner = buyer;
+    }
+    log_event_writer.send(message);
+    // stock has changed, so the menu has to be redrawn
+    shopping.set_changed();
+}
+
+/// Redraws the shop menu whenever the mode or the stock changes
+fn refresh_shop_menu(
+    mut cmd: Commands,
+    shopping: Res<Shopping>,
+    menu: Query<Entity, With<ShopMenu>>,
+    player: Query<(Entity, &Wallet), With<Player>>,
+    items: Query<(Entity, &Name, &InBackpack, &Price)>,
+) {
+    if !shopping.is_changed() {
+        return;
+    }
+
+    menu.iter()
+        .for_each(|entity| cmd.entity(entity).despawn_recursive());
+
+    let (player, wallet) = player.single();
+    let (title, seller) = match shopping.mode {
+        ShopMode::Buy => ("Buying (Tab to sell, Esc to leave)", shopping.vendor),
+        ShopMode::Sell => ("Selling (Tab to buy, Esc to leave)", player),
+    };
+    let wares = wares(seller, shopping.mode, items.iter());
+
+    let text_style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+
+    cmd.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35f32),
+                top: Val::Percent(20f32),
+                width: Val::Percent(30f32),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10f32)),
+                row_gap: Val::Px(5f32),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::rgba(0.16, 0.16, 0.16, 0.9)),
+            ..default()
+        },
+        ShopMenu,
+    ))
+    .with_children(|parent| {
+        parent.spawn(TextBundle::from_section(title, text_style(Color::GOLD)));
+        parent.spawn(TextBundle::from_section(
+            format!("Your gold: {}", wallet.gold),
+            text_style(Color::GOLD),
+        ));
+
+        if wares.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                "There is nothing to trade.",
+                text_style(DEFAULT_TEXT_COLOR),
+            ));
+        }
+
+        wares
+            .iter()
+            .take(ITEM_KEYS.len())
+            .enumerate()
+            .for_each(|(index, (_, name, price))| {
+                let color = match shopping.mode == ShopMode::Buy && *price > wallet.gold {
+                    true => Color::GRAY,
+                    false => DEFAULT_TEXT_COLOR,
+                };
+                parent.spawn(TextBundle::from_section(
+                    format!("[{}] {name} - {price} gold", index + 1),
+                    text_style(color),
+                ));
+            });
+    });
+}
+
+fn clean_up_shop(mut cmd: Commands, menu: Query<Entity, With<ShopMenu>>) {
+    menu.iter()
+        .for_each(|entity| cmd.entity(entity).despawn_recursive());
+    cmd.remove_resource::<Shopping>();
+}